@@ -0,0 +1,212 @@
+use crate::announce::Announce;
+use crate::bidding::{Bid, Bidding};
+use crate::card::{Card, Trump};
+use crate::coinche::Bids;
+use crate::view::PlayerView;
+use rand::seq::IteratorRandom;
+use rand::{Rng, RngCore};
+use std::fmt;
+
+/// Decisions taken by a seat during a deal.
+///
+/// Every answer is optional, `None` meaning the agent interrupted the game.
+/// Agents only see the [`PlayerView`] of their seat, so that they cannot know the hidden cards.
+/// Agents needing randomness must only draw from the given `rng`, so that a seeded game can be replayed.
+pub trait Agent: fmt::Debug {
+    /// First bidding turn : does the seat take the returned card ?
+    fn take(
+        &mut self,
+        view: &PlayerView,
+        card_returned: Card,
+        rng: &mut dyn RngCore,
+    ) -> Option<bool>;
+
+    /// Second bidding turn : which color, sans-atout or tout-atout does the seat choose,
+    /// `Some(None)` being a pass.
+    fn choose_color(
+        &mut self,
+        view: &PlayerView,
+        card_returned: Card,
+        rng: &mut dyn RngCore,
+    ) -> Option<Option<Trump>>;
+
+    /// First trick : which of its `announces` does the seat declare ? All of them by default.
+    fn declare(
+        &mut self,
+        _view: &PlayerView,
+        announces: &[Announce],
+        _rng: &mut dyn RngCore,
+    ) -> Option<Vec<Announce>> {
+        Some(announces.to_vec())
+    }
+
+    /// Does the seat, holding the trump king and queen, announce belote while playing the first
+    /// of them ? Staying silent forfeits the 20 points. Declared by default.
+    fn declare_belote(
+        &mut self,
+        _view: &PlayerView,
+        _card: Card,
+        _rng: &mut dyn RngCore,
+    ) -> Option<bool> {
+        Some(true)
+    }
+
+    /// Which card does the seat play among `choices` ?
+    fn choose_card(
+        &mut self,
+        view: &PlayerView,
+        choices: &[Card],
+        rng: &mut dyn RngCore,
+    ) -> Option<Card>;
+
+    /// Coinche auction : which bid does the seat make, among `bids.legal(position)` ?
+    fn bid(&mut self, _view: &PlayerView, _bids: &Bids, _rng: &mut dyn RngCore) -> Option<Bid> {
+        Some(Bid::Pass)
+    }
+
+    /// Is a human sitting behind this agent ?
+    fn is_human(&self) -> bool {
+        false
+    }
+}
+
+/// Agent taking with a fixed probability and playing any legal card.
+#[derive(Debug, Clone, Copy)]
+pub struct Random {
+    randomization: f64,
+}
+
+impl Random {
+    /// `randomization` is the probability to take at each bidding turn.
+    pub const fn new(randomization: f64) -> Self {
+        Self { randomization }
+    }
+
+    /// Random agent for a table of `random_players` random agents, so that one of them takes on average.
+    pub fn for_table(random_players: usize) -> Self {
+        let random_players = f64::from(u32::try_from(random_players).unwrap_or(u32::MAX));
+        if random_players.is_normal() {
+            Self::new(1.0 / random_players)
+        } else {
+            Self::new(0.0)
+        }
+    }
+
+    pub const fn randomization(&self) -> f64 {
+        self.randomization
+    }
+}
+
+impl Agent for Random {
+    fn take(&mut self, _: &PlayerView, _: Card, rng: &mut dyn RngCore) -> Option<bool> {
+        Some(rng.gen_bool(self.randomization))
+    }
+
+    fn choose_color(
+        &mut self,
+        _: &PlayerView,
+        card_returned: Card,
+        rng: &mut dyn RngCore,
+    ) -> Option<Option<Trump>> {
+        if rng.gen_bool(self.randomization) {
+            Some(
+                Bidding::second_turn_trumps(card_returned)
+                    .map(Some)
+                    .chain([None])
+                    .choose(rng)
+                    .flatten(),
+            )
+        } else {
+            Some(None)
+        }
+    }
+
+    fn choose_card(
+        &mut self,
+        _: &PlayerView,
+        choices: &[Card],
+        rng: &mut dyn RngCore,
+    ) -> Option<Card> {
+        choices.iter().choose(rng).copied()
+    }
+
+    /// Passes, or makes the cheapest announce, coinche or surcoinche.
+    fn bid(&mut self, view: &PlayerView, bids: &Bids, rng: &mut dyn RngCore) -> Option<Bid> {
+        if !rng.gen_bool(self.randomization) {
+            return Some(Bid::Pass);
+        }
+        let legal = bids.legal(view.position());
+        let cheapest = legal.iter().find_map(|bid| match bid {
+            Bid::Announce { level, .. } => Some(*level),
+            _ => None,
+        });
+        legal
+            .into_iter()
+            .filter(|bid| match bid {
+                Bid::Announce { level, .. } => Some(*level) == cheapest,
+                Bid::Coinche | Bid::Surcoinche => true,
+                Bid::Take | Bid::Color(_) | Bid::Pass => false,
+            })
+            .choose(rng)
+            .or(Some(Bid::Pass))
+    }
+}
+
+#[test]
+fn agent_tests() {
+    use crate::bidding::PlayOrNext;
+    use crate::game::Game;
+    use crate::order::Order;
+    use crate::player::Player;
+    use crate::players::Players;
+    use crate::playing::NextGameOrInterrupt;
+    use crate::position::Position;
+    use crate::team::Team;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[derive(Debug)]
+    struct First;
+
+    impl Agent for First {
+        fn take(&mut self, _: &PlayerView, _: Card, _: &mut dyn RngCore) -> Option<bool> {
+            Some(true)
+        }
+        fn choose_color(
+            &mut self,
+            _: &PlayerView,
+            _: Card,
+            _: &mut dyn RngCore,
+        ) -> Option<Option<Trump>> {
+            Some(None)
+        }
+        fn choose_card(
+            &mut self,
+            _: &PlayerView,
+            choices: &[Card],
+            _: &mut dyn RngCore,
+        ) -> Option<Card> {
+            choices.first().copied()
+        }
+    }
+
+    let players = Players::new(
+        Player::from(First),
+        Player::from(First),
+        Player::from(First),
+        Player::from(First),
+    );
+    let game = Game::default(players, Order::default(), StdRng::seed_from_u64(0));
+    let Ok(bidding) = game.distribute().and_then(Game::bidding) else {
+        panic!("cannot deal");
+    };
+    let Ok(PlayOrNext::PlayGame(playing)) = bidding.playing_game_or_redistribute() else {
+        panic!("first player should take");
+    };
+    assert!(playing.taker() == Position::North);
+    let Ok(NextGameOrInterrupt::NextGame(next_game)) = playing.play() else {
+        panic!("deal should be played");
+    };
+    let points = next_game.points();
+    assert!(points[Team::NorthSouth] + points[Team::WestEast] >= 162);
+}
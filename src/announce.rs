@@ -0,0 +1,197 @@
+use crate::card::{Card, Color, Trump, Value};
+use crate::position::Position;
+use crate::team::Team;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use strum::IntoEnumIterator;
+
+/// Values in the order used by sequences, whatever the trumps.
+pub const SEQUENCE: [Value; 8] = [
+    Value::_7,
+    Value::_8,
+    Value::_9,
+    Value::_10,
+    Value::Jack,
+    Value::Queen,
+    Value::King,
+    Value::As,
+];
+
+/// Rank of `value` inside a sequence, the 7 being 0 and the ace 7.
+pub fn sequence_rank(value: Value) -> u8 {
+    SEQUENCE
+        .iter()
+        .position(|v| *v == value)
+        .and_then(|rank| u8::try_from(rank).ok())
+        .unwrap_or_default()
+}
+
+/// Declaration made during the first trick.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Announce {
+    /// `length` cards following each other in `color`, up to `high` : tierce, cinquante or cent.
+    Sequence {
+        color: Color,
+        length: u8,
+        high: Value,
+    },
+    /// The four cards of `value`, only from the queen up and the nine.
+    Square(Value),
+}
+
+impl Announce {
+    pub const fn points(&self) -> u64 {
+        match self {
+            Self::Sequence { length: 3, .. } => 20,
+            Self::Sequence { length: 4, .. } => 50,
+            Self::Square(Value::Jack) => 200,
+            Self::Square(Value::_9) => 150,
+            Self::Sequence { .. } | Self::Square(_) => 100,
+        }
+    }
+
+    /// Cards shown by the announce.
+    pub fn cards(self) -> Vec<Card> {
+        match self {
+            Self::Sequence {
+                color,
+                length,
+                high,
+            } => {
+                let high = usize::from(sequence_rank(high));
+                let low = (high + 1).saturating_sub(usize::from(length));
+                SEQUENCE[low..=high]
+                    .iter()
+                    .filter_map(|value| Card::new(color, *value))
+                    .collect()
+            }
+            Self::Square(value) => Color::iter()
+                .filter_map(|color| Card::new(color, value))
+                .collect(),
+        }
+    }
+
+    /// Key ordering announces : points first, a square beating a sequence of the same points,
+    /// then the longest sequence, the highest card and finally a sequence in trumps.
+    fn strength(self, trump: Trump) -> (u64, bool, u8, u8, bool) {
+        match self {
+            Self::Sequence {
+                color,
+                length,
+                high,
+            } => (
+                self.points(),
+                false,
+                length,
+                sequence_rank(high),
+                trump.color() == Some(color),
+            ),
+            Self::Square(value) => {
+                let rank = match value {
+                    Value::As => 3,
+                    Value::_10 => 2,
+                    Value::King => 1,
+                    _ => 0,
+                };
+                (self.points(), true, 4, rank, false)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Announce {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Sequence {
+                color,
+                length: 3,
+                high,
+            } => write!(f, "Tierce to {color} {high}"),
+            Self::Sequence {
+                color,
+                length: 4,
+                high,
+            } => write!(f, "Cinquante to {color} {high}"),
+            Self::Sequence { color, high, .. } => write!(f, "Cent to {color} {high}"),
+            Self::Square(value) => write!(f, "Square of {value}"),
+        }
+    }
+}
+
+/// Only the team holding the best announce scores, all its announces then count.
+/// Nobody scores when the best announces of both teams are equal.
+pub fn score(declared: &[(Position, Announce)], trump: Trump) -> Option<(Team, u64)> {
+    let best = |team: Team| {
+        declared
+            .iter()
+            .filter(|(position, _)| position.team() == team)
+            .map(|(_, announce)| announce.strength(trump))
+            .max()
+    };
+    let north_south = best(Team::NorthSouth);
+    let west_east = best(Team::WestEast);
+    let winner = match north_south.cmp(&west_east) {
+        std::cmp::Ordering::Greater => Team::NorthSouth,
+        std::cmp::Ordering::Less => Team::WestEast,
+        std::cmp::Ordering::Equal => return None,
+    };
+    let points = declared
+        .iter()
+        .filter(|(position, _)| position.team() == winner)
+        .map(|(_, announce)| announce.points())
+        .sum();
+    Some((winner, points))
+}
+
+#[test]
+fn announce_tests() {
+    use crate::hand::Hand;
+    use std::str::FromStr;
+
+    let mut hand = Hand::default();
+    for card in ["♥7", "♥8", "♥9", "♥10", "♠J", "♦J", "♣J", "♥J"] {
+        let Ok(card) = Card::from_str(card) else {
+            panic!("bad card {card}");
+        };
+        let _ = hand.take(card);
+    }
+    let announces = hand.announces();
+    assert!(announces.contains(&Announce::Square(Value::Jack)));
+    assert!(announces.contains(&Announce::Sequence {
+        color: Color::Heart,
+        length: 5,
+        high: Value::Jack
+    }));
+    assert_eq!(announces.len(), 2);
+    let tierce = Announce::Sequence {
+        color: Color::Heart,
+        length: 3,
+        high: Value::_9,
+    };
+    assert_eq!(tierce.cards().len(), 3);
+    assert!(tierce.cards().iter().all(|card| hand.contains(card)));
+
+    let tierce = |color, high| Announce::Sequence {
+        color,
+        length: 3,
+        high,
+    };
+    let declared = [
+        (Position::North, tierce(Color::Spade, Value::King)),
+        (Position::South, Announce::Square(Value::Queen)),
+        (Position::East, tierce(Color::Club, Value::As)),
+    ];
+    assert_eq!(
+        score(&declared, Trump::Color(Color::Heart)),
+        Some((Team::NorthSouth, 120))
+    );
+    let declared = [
+        (Position::North, tierce(Color::Spade, Value::As)),
+        (Position::East, tierce(Color::Club, Value::As)),
+    ];
+    assert_eq!(score(&declared, Trump::NoTrump), None);
+    assert_eq!(
+        score(&declared, Trump::Color(Color::Club)),
+        Some((Team::WestEast, 20))
+    );
+}
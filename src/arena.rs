@@ -0,0 +1,425 @@
+use crate::errors::BeloteErrorKind;
+use crate::event::GameEvent;
+use crate::game::Game;
+use crate::order::Order;
+use crate::player::Player;
+use crate::players::Players;
+use crate::points::Points;
+use crate::position::Position;
+use crate::rules::RuleSet;
+use crate::statistics::ratio;
+use crate::team::Team;
+use crate::variant::Variant;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::Serialize;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use strum::IntoEnumIterator;
+
+/// Rating every entrant starts with.
+const ELO: f64 = 1500.0;
+/// Weight of a single deal on the ratings.
+const ELO_FACTOR: f64 = 16.0;
+/// Normal quantile of a 95% confidence interval.
+const CONFIDENCE: f64 = 1.96;
+
+/// Agent taking part in an [`Arena`], both seats of its team being built anew for every deal.
+pub struct Entrant {
+    name: String,
+    build: Box<dyn Fn() -> Player>,
+}
+
+impl Entrant {
+    /// Refuses an agent played by a human, who could not sit through thousands of deals.
+    pub fn new(
+        name: impl Into<String>,
+        build: impl Fn() -> Player + 'static,
+    ) -> Result<Self, BeloteErrorKind> {
+        let name = name.into();
+        if build().agent().is_human() {
+            return Err(BeloteErrorKind::InvalidCase(format!(
+                "{name} is played by a human, who cannot enter an arena"
+            )));
+        }
+        Ok(Self {
+            name,
+            build: Box::new(build),
+        })
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Debug for Entrant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Entrant")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Estimate with the margin of its 95% confidence interval.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    value: f64,
+    margin: f64,
+}
+
+impl Interval {
+    pub const fn value(&self) -> f64 {
+        self.value
+    }
+    pub const fn margin(&self) -> f64 {
+        self.margin
+    }
+}
+
+/// Results of an entrant over the arena, a deal being won by the team scoring the most points.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Standing {
+    name: String,
+    deals: u64,
+    wins: u64,
+    draws: u64,
+    losses: u64,
+    points: u64,
+    /// Sum of the squared points of every deal, for their variance.
+    squares: u64,
+    takes: u64,
+    made: u64,
+    elo: f64,
+}
+
+impl Standing {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            deals: 0,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+            points: 0,
+            squares: 0,
+            takes: 0,
+            made: 0,
+            elo: ELO,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Scored deals played, those dealt again excluded.
+    pub const fn deals(&self) -> u64 {
+        self.deals
+    }
+    pub const fn wins(&self) -> u64 {
+        self.wins
+    }
+    pub const fn draws(&self) -> u64 {
+        self.draws
+    }
+    pub const fn losses(&self) -> u64 {
+        self.losses
+    }
+    /// Contracts taken by its team.
+    pub const fn takes(&self) -> u64 {
+        self.takes
+    }
+    pub const fn made(&self) -> u64 {
+        self.made
+    }
+    pub const fn elo(&self) -> f64 {
+        self.elo
+    }
+
+    /// Share of the deals won, a draw counting as half a win.
+    pub fn win_rate(&self) -> Interval {
+        let rate = ratio(2 * self.wins + self.draws, 2 * self.deals);
+        Interval {
+            value: rate,
+            margin: CONFIDENCE * (rate * (1.0 - rate) * ratio(1, self.deals)).sqrt(),
+        }
+    }
+    pub fn average_points(&self) -> Interval {
+        let average = ratio(self.points, self.deals);
+        let variance = average.mul_add(-average, ratio(self.squares, self.deals));
+        Interval {
+            value: average,
+            margin: CONFIDENCE * (variance.max(0.0) * ratio(1, self.deals)).sqrt(),
+        }
+    }
+    /// Share of the contracts taken and made.
+    pub fn success_rate(&self) -> f64 {
+        ratio(self.made, self.takes)
+    }
+
+    fn record(&mut self, ours: u64, theirs: u64, took: bool, made: bool) {
+        self.deals += 1;
+        match ours.cmp(&theirs) {
+            std::cmp::Ordering::Greater => self.wins += 1,
+            std::cmp::Ordering::Equal => self.draws += 1,
+            std::cmp::Ordering::Less => self.losses += 1,
+        }
+        self.points += ours;
+        self.squares += ours * ours;
+        if took {
+            self.takes += 1;
+            if made {
+                self.made += 1;
+            }
+        }
+    }
+}
+
+impl fmt::Display for Standing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let win_rate = self.win_rate();
+        let average = self.average_points();
+        write!(
+            f,
+            "{} : elo {:.0}, {} deals, {:.1}% ± {:.1} won, {:.1} ± {:.1} points per deal, {:.1}% of {} contracts made",
+            self.name,
+            self.elo,
+            self.deals,
+            100.0 * win_rate.value(),
+            100.0 * win_rate.margin(),
+            average.value(),
+            average.margin(),
+            100.0 * self.success_rate(),
+            self.takes,
+        )
+    }
+}
+
+/// Scored deal between two teams.
+struct Deal {
+    taker: Team,
+    made: bool,
+    points: Points,
+}
+
+/// Tournament where every pair of entrants plays the same deals, seats and dealer rotating.
+///
+/// Deal `n` is dealt from `seed + n` and opens by the next seat each time. A pair swaps its
+/// teams every deal, or plays both sides of each deal in duplicate, luck then cancelling out.
+#[derive(Debug)]
+pub struct Arena {
+    entrants: Vec<Entrant>,
+    deals: u64,
+    duplicate: bool,
+    seed: u64,
+    variant: Variant,
+    rules: RuleSet,
+}
+
+impl Arena {
+    /// Arena of `entrants`, each pair playing `deals` deals.
+    pub fn new(entrants: Vec<Entrant>, deals: u64) -> Self {
+        Self {
+            entrants,
+            deals,
+            duplicate: false,
+            seed: 0,
+            variant: Variant::default(),
+            rules: RuleSet::default(),
+        }
+    }
+
+    pub fn entrants(&self) -> &[Entrant] {
+        &self.entrants
+    }
+    pub const fn deals(&self) -> u64 {
+        self.deals
+    }
+    pub const fn duplicate(&self) -> bool {
+        self.duplicate
+    }
+    pub const fn set_duplicate(&mut self, duplicate: bool) {
+        self.duplicate = duplicate;
+    }
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+    pub const fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+    pub const fn variant(&self) -> Variant {
+        self.variant
+    }
+    pub const fn set_variant(&mut self, variant: Variant) {
+        self.variant = variant;
+    }
+    pub const fn rules(&self) -> RuleSet {
+        self.rules
+    }
+    pub const fn set_rules(&mut self, rules: RuleSet) {
+        self.rules = rules;
+    }
+
+    /// Plays every deal, then ranks the entrants by rating.
+    pub fn run(&self) -> Result<Vec<Standing>, BeloteErrorKind> {
+        let mut standings: Vec<Standing> = self
+            .entrants
+            .iter()
+            .map(|entrant| Standing::new(entrant.name()))
+            .collect();
+        for first in 0..self.entrants.len() {
+            for second in first + 1..self.entrants.len() {
+                for deal in 0..self.deals {
+                    let seed = self.seed.wrapping_add(deal);
+                    let dealer = Position::iter()
+                        .cycle()
+                        .nth(usize::try_from(deal % 4).unwrap_or_default())
+                        .unwrap_or(Position::North);
+                    let seatings: &[bool] = if self.duplicate {
+                        &[false, true]
+                    } else if deal % 2 == 0 {
+                        &[false]
+                    } else {
+                        &[true]
+                    };
+                    for swapped in seatings {
+                        let (north_south, west_east) = if *swapped {
+                            (second, first)
+                        } else {
+                            (first, second)
+                        };
+                        let Some(result) = self.play(north_south, west_east, seed, dealer)? else {
+                            continue;
+                        };
+                        Self::score(&mut standings, north_south, west_east, &result);
+                    }
+                }
+            }
+        }
+        standings.sort_by(|a, b| b.elo.total_cmp(&a.elo));
+        Ok(standings)
+    }
+
+    /// Plays a single deal, `None` when it is dealt again or interrupted.
+    fn play(
+        &self,
+        north_south: usize,
+        west_east: usize,
+        seed: u64,
+        dealer: Position,
+    ) -> Result<Option<Deal>, BeloteErrorKind> {
+        let north_south = &self.entrants[north_south].build;
+        let west_east = &self.entrants[west_east].build;
+        let players = Players::new(north_south(), north_south(), west_east(), west_east());
+        let mut game = Game::default(
+            players,
+            Order::starting(dealer),
+            StdRng::seed_from_u64(seed),
+        );
+        game.set_variant(self.variant);
+        game.set_rules(self.rules);
+        let events = Arc::new(Mutex::new(Vec::new()));
+        game.observe(events.clone());
+        if game.play_deal()?.is_none() {
+            return Ok(None);
+        }
+        let Ok(events) = events.lock() else {
+            return Ok(None);
+        };
+        Ok(events.iter().find_map(|event| match *event {
+            GameEvent::DealScored {
+                taker,
+                outcome,
+                points,
+                ..
+            } => Some(Deal {
+                taker: taker.team(),
+                made: outcome.is_made(),
+                points,
+            }),
+            _ => None,
+        }))
+    }
+
+    fn score(standings: &mut [Standing], north_south: usize, west_east: usize, deal: &Deal) {
+        let expected = |ours: f64, theirs: f64| 1.0 / (1.0 + 10f64.powf((theirs - ours) / 400.0));
+        let (north_south_elo, west_east_elo) =
+            (standings[north_south].elo, standings[west_east].elo);
+        for (index, team, expected) in [
+            (
+                north_south,
+                Team::NorthSouth,
+                expected(north_south_elo, west_east_elo),
+            ),
+            (
+                west_east,
+                Team::WestEast,
+                expected(west_east_elo, north_south_elo),
+            ),
+        ] {
+            let (ours, theirs) = (deal.points[team], deal.points[team.other()]);
+            let standing = &mut standings[index];
+            standing.record(ours, theirs, deal.taker == team, deal.made);
+            let result = match ours.cmp(&theirs) {
+                std::cmp::Ordering::Greater => 1.0,
+                std::cmp::Ordering::Equal => 0.5,
+                std::cmp::Ordering::Less => 0.0,
+            };
+            standing.elo = ELO_FACTOR.mul_add(result - expected, standing.elo);
+        }
+    }
+}
+
+#[test]
+fn arena_tests() {
+    use crate::agent::{Agent, Random};
+    use crate::card::{Card, Trump};
+    use crate::heuristic::Heuristic;
+    use crate::view::PlayerView;
+    use rand::RngCore;
+
+    #[derive(Debug)]
+    struct Human;
+
+    impl Agent for Human {
+        fn take(&mut self, _: &PlayerView, _: Card, _: &mut dyn RngCore) -> Option<bool> {
+            None
+        }
+        fn choose_color(
+            &mut self,
+            _: &PlayerView,
+            _: Card,
+            _: &mut dyn RngCore,
+        ) -> Option<Option<Trump>> {
+            None
+        }
+        fn choose_card(&mut self, _: &PlayerView, _: &[Card], _: &mut dyn RngCore) -> Option<Card> {
+            None
+        }
+        fn is_human(&self) -> bool {
+            true
+        }
+    }
+
+    assert!(Entrant::new("human", || Player::from(Human)).is_err());
+    let (Ok(random), Ok(heuristic)) = (
+        Entrant::new("random", || Player::from(Random::for_table(2))),
+        Entrant::new("heuristic", || Player::from(Heuristic)),
+    ) else {
+        panic!("agents should enter the arena");
+    };
+    let entrants = vec![random, heuristic];
+    let mut arena = Arena::new(entrants, 40);
+    arena.set_duplicate(true);
+    arena.set_seed(3);
+    let Ok(standings) = arena.run() else {
+        panic!("the arena should be playable");
+    };
+    assert_eq!(standings.len(), 2);
+    assert_eq!(standings[0].name(), "heuristic");
+    assert_eq!(standings[0].deals(), standings[1].deals());
+    assert_eq!(standings[0].wins(), standings[1].losses());
+    assert!(standings[0].elo() > ELO && standings[1].elo() < ELO);
+    assert!(standings[0].average_points().value() > standings[1].average_points().value());
+    let win_rate = standings[0].win_rate();
+    assert!(win_rate.margin() > 0.0 && win_rate.value() - win_rate.margin() > 0.5);
+}
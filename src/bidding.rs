@@ -1,39 +1,95 @@
-use crate::card::{Card, Color};
-use crate::contract::Contract;
+use crate::card::{Card, Trump};
+use crate::coinche::Level;
+use crate::constants::MAX_PLAYERS;
+use crate::engine::{Action, Decision, Step};
 use crate::errors::BeloteErrorKind;
+use crate::event::GameEvent;
 use crate::game::Game;
 use crate::hand::Hand;
 use crate::hands::Hands;
 use crate::initial::Initial;
 use crate::playing::Playing;
 use crate::position::Position;
-use derive_more::{Constructor, Deref, DerefMut};
-use inquire::{Confirm, Select};
-use rand::{seq::IteratorRandom, thread_rng, Rng};
-use std::str::FromStr;
-use strum::IntoEnumIterator;
+use derive_more::{Deref, DerefMut};
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use tracing::info;
 
+/// Outcome of the bidding : a taker was found, or the cards are gathered for the next deal.
 pub enum PlayOrNext {
     NextGame(Game<Initial>),
     PlayGame(Game<Playing>),
     Interrupted,
 }
 
-#[derive(Constructor, Deref, DerefMut)]
+/// Decision of a player during the bidding.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Bid {
+    /// First turn : the returned card is taken.
+    Take,
+    /// Second turn : the given color, sans-atout or tout-atout is chosen as trumps.
+    Color(Trump),
+    /// Coinche : the team commits to make `level` points with `trump`.
+    Announce {
+        #[serde(alias = "contract")]
+        level: Level,
+        trump: Trump,
+    },
+    /// Coinche : the last announce of the opponents is doubled.
+    Coinche,
+    /// Coinche : the coinched announce of the team is doubled again.
+    Surcoinche,
+    Pass,
+}
+
+impl fmt::Display for Bid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Take => write!(f, "Take"),
+            Self::Color(color) => write!(f, "{color}"),
+            Self::Announce { level, trump } => write!(f, "{level} {trump}"),
+            Self::Coinche => write!(f, "Coinche"),
+            Self::Surcoinche => write!(f, "Surcoinche"),
+            Self::Pass => write!(f, "Pass"),
+        }
+    }
+}
+
+/// Phase where players decide to take the returned card or not.
+#[derive(Deref, DerefMut)]
 pub struct Bidding {
     card_returned: Card,
     hands: Hands,
+    /// Bids made so far, the first round being the first 4 of them.
+    bids: usize,
     #[deref]
     #[deref_mut]
     initial: Initial,
 }
 
 impl Bidding {
+    pub const fn new(card_returned: Card, hands: Hands, initial: Initial) -> Self {
+        Self {
+            card_returned,
+            hands,
+            bids: 0,
+            initial,
+        }
+    }
+
     pub const fn into(self) -> Initial {
         self.initial
     }
 
+    pub const fn card_returned(&self) -> Card {
+        self.card_returned
+    }
+
+    /// Trumps which may be chosen during the second turn : any but the color of `card_returned`.
+    pub fn second_turn_trumps(card_returned: Card) -> impl Iterator<Item = Trump> {
+        Trump::all().filter(move |trump| *trump != Trump::Color(card_returned.color()))
+    }
+
     pub fn hand(&self, position: Position) -> Hand {
         self.hands[position]
     }
@@ -56,126 +112,112 @@ impl Bidding {
 }
 
 impl Game<Bidding> {
+    /// Runs the two bidding turns, then completes the hands when somebody took.
     pub fn playing_game_or_redistribute(mut self) -> Result<PlayOrNext, BeloteErrorKind> {
-        let order = self.order();
-        let players = self.players();
-        let points = self.points();
-        let mut rng = thread_rng();
-        let card_returned = self.card_returned;
-        let mut trump_color = card_returned.color();
-        let mut taker: Option<Position> = None;
-        info!("First bidding turn");
-        for position in order {
-            let take = if players[position].random() {
-                rng.gen_bool(players.randomization())
-            } else {
-                'questionnaire: loop {
-                    info!(
-                        "{position} must decide if he is taking : {}",
-                        self.hand(position)
-                    );
-                    let answer = Confirm::new("Do you take ? (ESC to cancel)")
-                        .with_default(false)
-                        .prompt_skippable();
-                    match answer {
-                        Ok(Some(value)) => {
-                            break 'questionnaire value;
-                        }
-                        Ok(None) => {
-                            info!("Interrupted.");
-                            return Ok(PlayOrNext::Interrupted);
-                        }
-                        Err(_) => {
-                            info!("Error with questionnaire, try again.");
-                        }
-                    };
-                }
+        loop {
+            let decision = self.pending_decision();
+            let Some(action) = self.decide(&decision) else {
+                info!("Interrupted.");
+                return Ok(PlayOrNext::Interrupted);
             };
+            self = match self.apply(action)? {
+                Step::Pending(game) => game,
+                Step::Over(play_or_next) => return Ok(play_or_next),
+            };
+        }
+    }
 
-            if take {
-                taker = Some(position);
-                trump_color = card_returned.color();
-                break;
+    /// Next seat to bid : each seat in order decides to take the returned card, then to choose
+    /// another trump when nobody did.
+    pub fn pending_decision(&self) -> Decision {
+        let position = self.order()[self.bids % MAX_PLAYERS];
+        let hand = self.hand(position);
+        let card_returned = self.card_returned;
+        if self.bids < MAX_PLAYERS {
+            Decision::Take {
+                position,
+                hand,
+                card_returned,
             }
-            info!("{position} did not take at first glance");
-        }
-        info!("randomization: {}", players.randomization());
-        if taker.is_none() {
-            info!("Second bidding turn");
-            'second_turn: for position in order {
-                let chosen_color = if players[position].random() {
-                    if rng.gen_bool(players.randomization()) {
-                        Contract::iter()
-                            .choose(&mut rng)
-                            .and_then(|contract| contract.color())
-                    } else {
-                        None
-                    }
-                } else {
-                    'choose_contract: loop {
-                        let card_returned_color = card_returned.color();
-                        let contracts: Vec<String> = Contract::iter()
-                            .filter(|c| c.to_string() != card_returned_color.to_string())
-                            .map(|c| c.to_string())
-                            .collect();
-                        info!("Nobody took: {card_returned}, please choose a color for trumps");
-                        let answer =
-                            Select::new("Which color do you choose ? (ESC to cancel)", contracts)
-                                .prompt_skippable();
-                        match answer {
-                            Ok(Some(maybe_chosen_color)) => {
-                                if let Ok(chosen_color) = Color::from_str(&maybe_chosen_color) {
-                                    break 'choose_contract Some(chosen_color);
-                                }
-                                continue 'second_turn;
-                            }
-                            Ok(None) => {
-                                info!("Interrupted.");
-                                return Ok(PlayOrNext::Interrupted);
-                            }
-                            Err(_) => {
-                                info!("Error with questionnaire, try again.");
-                            }
-                        };
-                    }
-                };
-                if let Some(chosen_color) = chosen_color {
-                    taker = Some(position);
-                    trump_color = chosen_color;
-                    break;
-                }
+        } else {
+            Decision::ChooseColor {
+                position,
+                hand,
+                card_returned,
+                trumps: Bidding::second_turn_trumps(card_returned).collect(),
             }
         }
-        let Some(taker) = taker else {
-            self.stack_mut().append_card(card_returned)?;
-            for position in order {
-                for card in self.hand_mut(position).into_iter().flatten() {
-                    self.stack_mut().append_card(card)?;
+    }
+
+    /// Plays the bid of the seat to decide, completing the hands once somebody took or
+    /// gathering the cards back when nobody did at the end of the second turn.
+    #[allow(clippy::needless_pass_by_value)] // Every phase takes its action the same way.
+    pub fn apply(mut self, action: Action) -> Result<Step<Bidding, PlayOrNext>, BeloteErrorKind> {
+        let decision = self.pending_decision();
+        decision.check(&action)?;
+        let position = decision.position();
+        let trump = match action {
+            Action::Take(take) => {
+                self.notify(GameEvent::BidMade {
+                    position,
+                    bid: if take { Bid::Take } else { Bid::Pass },
+                });
+                if !take {
+                    info!("{position} did not take at first glance");
                 }
+                take.then_some(Trump::Color(self.card_returned.color()))
+            }
+            Action::ChooseColor(chosen_trump) => {
+                self.notify(GameEvent::BidMade {
+                    position,
+                    bid: chosen_trump.map_or(Bid::Pass, Bid::Color),
+                });
+                chosen_trump
             }
-            // deck.append_stack(self.stack_mut());
-            let initial = self.into().into().next();
-            return Ok(PlayOrNext::NextGame(Game::new(players, points, initial)));
+            _ => None,
         };
+        if let Some(trump) = trump {
+            return self.take(position, trump).map(Step::Over);
+        }
+
+        self.bids += 1;
+        if self.bids == MAX_PLAYERS {
+            info!("Nobody took {}, second bidding turn", self.card_returned);
+        }
+        if self.bids < 2 * MAX_PLAYERS {
+            return Ok(Step::Pending(self));
+        }
+        let card_returned = self.card_returned;
+        self.stack_mut().append_card(card_returned)?;
+        for position in self.order() {
+            for card in self.hand(position) {
+                self.stack_mut().append_card(card)?;
+            }
+        }
+        Ok(Step::Over(PlayOrNext::NextGame(
+            self.map(|bidding| bidding.into().next()),
+        )))
+    }
 
-        info!("{taker} for color {trump_color}, we give him {card_returned}");
-        let mut bidding = self.into();
-        bidding.hand_mut(taker).take(card_returned)?;
+    /// Gives the returned card to `taker`, then completes every hand.
+    fn take(mut self, taker: Position, trump: Trump) -> Result<PlayOrNext, BeloteErrorKind> {
+        let card_returned = self.card_returned;
+        info!("{taker} for trump {trump}, we give him {card_returned}");
+        self.notify(GameEvent::TrumpChosen { taker, trump });
+        self.hand_mut(taker).take(card_returned)?;
 
-        for position in order {
+        for position in self.order() {
             if position == taker {
                 info!("Giving {position} 2 more cards because taker");
-                bidding.complete_hand(position, 2)?;
+                self.complete_hand(position, 2)?;
             } else {
                 info!("Giving {position} 3 more cards because others");
-                bidding.complete_hand(position, 3)?;
+                self.complete_hand(position, 3)?;
             }
         }
 
-        Ok(PlayOrNext::PlayGame(Game::new(
-            players,
-            points,
-            Playing::new(taker, bidding.hands, trump_color, bidding.into()),
-        )))
+        Ok(PlayOrNext::PlayGame(self.map(|bidding| {
+            Playing::new(taker, bidding.hands, trump, None, bidding.into())
+        })))
     }
 }
@@ -1,12 +1,16 @@
 use crate::errors::BeloteErrorKind;
 use colored::Colorize;
 use core::fmt::Display;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 // use std::iter::FromIterator;
 use std::str::FromStr;
+use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumIter};
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, EnumIter, Hash)]
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, EnumIter, Hash, Serialize, Deserialize,
+)]
 pub enum Color {
     Heart,
     Spade,
@@ -14,6 +18,18 @@ pub enum Color {
     Club,
 }
 
+impl Color {
+    /// Plain name, stable across displays.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Heart => "heart",
+            Self::Spade => "spade",
+            Self::Diamond => "diamond",
+            Self::Club => "club",
+        }
+    }
+}
+
 impl Display for Color {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let repr = match self {
@@ -44,7 +60,80 @@ impl FromStr for Color {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, EnumIter, Display, Hash)]
+/// Trumps of a deal : a single color, no trump at all (sans-atout) or every color (tout-atout).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Trump {
+    Color(Color),
+    NoTrump,
+    AllTrump,
+}
+
+impl Trump {
+    /// Every possible trump, colors first.
+    pub fn all() -> impl Iterator<Item = Self> {
+        Color::iter()
+            .map(Self::Color)
+            .chain([Self::NoTrump, Self::AllTrump])
+    }
+    pub fn is_trump(self, color: Color) -> bool {
+        match self {
+            Self::Color(trump_color) => trump_color == color,
+            Self::NoTrump => false,
+            Self::AllTrump => true,
+        }
+    }
+    pub const fn color(self) -> Option<Color> {
+        match self {
+            Self::Color(color) => Some(color),
+            Self::NoTrump | Self::AllTrump => None,
+        }
+    }
+    /// Plain name, stable across displays.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Color(color) => color.name(),
+            Self::NoTrump => "notrump",
+            Self::AllTrump => "alltrump",
+        }
+    }
+}
+
+impl Display for Trump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Color(color) => write!(f, "{color}"),
+            Self::NoTrump => write!(f, "SA"),
+            Self::AllTrump => write!(f, "TA"),
+        }
+    }
+}
+
+impl FromStr for Trump {
+    type Err = BeloteErrorKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "SA" => Ok(Self::NoTrump),
+            "TA" => Ok(Self::AllTrump),
+            _ => Color::from_str(s).map(Self::Color),
+        }
+    }
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    EnumIter,
+    Display,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
 pub enum Value {
     #[strum(serialize = "7")]
     _7,
@@ -82,7 +171,21 @@ impl FromStr for Value {
     }
 }
 
-#[derive(Copy, Ord, Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+/// Colors and values by index, in the order of their declaration.
+const COLORS: [Color; 4] = [Color::Heart, Color::Spade, Color::Diamond, Color::Club];
+const VALUES: [Value; 8] = [
+    Value::_7,
+    Value::_8,
+    Value::_9,
+    Value::Jack,
+    Value::Queen,
+    Value::King,
+    Value::_10,
+    Value::As,
+];
+
+/// One of the 32 cards of a belote stack.
+#[derive(Copy, Ord, Clone, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
 pub struct Card {
     color: Color,
     value: Value,
@@ -98,66 +201,82 @@ impl Card {
     pub const fn new(color: Color, value: Value) -> Option<Self> {
         Some(Self { color, value })
     }
-    pub fn points(&self, trump_color: Color) -> u64 {
-        match self.value {
-            Value::Jack => {
-                if self.color == trump_color {
-                    20
-                } else {
-                    2
-                }
-            }
-            Value::_9 => {
-                if self.color == trump_color {
-                    14
-                } else {
-                    0
-                }
-            }
-            Value::As => 11,
-            Value::_10 => 10,
-            Value::King => 4,
-            Value::Queen => 3,
-            Value::_7 | Value::_8 => 0,
+    /// The 32 cards of the game.
+    pub fn all() -> impl Iterator<Item = Self> {
+        Color::iter().flat_map(|color| Value::iter().map(move |value| Self { color, value }))
+    }
+    /// Points of the card once won in a trick, every mode summing to 152 points for the 32 cards.
+    pub fn points(&self, trump: Trump) -> u64 {
+        match (trump, self.value) {
+            (Trump::AllTrump, Value::Jack) => 14,
+            (Trump::AllTrump, Value::_9) => 9,
+            (Trump::AllTrump, Value::As) => 6,
+            (Trump::AllTrump, Value::_10) => 5,
+            (Trump::AllTrump, Value::King) => 3,
+            (Trump::AllTrump, Value::Queen) => 1,
+            (Trump::NoTrump, Value::As) => 19,
+            (Trump::Color(color), Value::Jack) if color == self.color => 20,
+            (Trump::Color(color), Value::_9) if color == self.color => 14,
+            (_, Value::As) => 11,
+            (_, Value::_10) => 10,
+            (_, Value::King) => 4,
+            (_, Value::Queen) => 3,
+            (_, Value::Jack) => 2,
+            (_, Value::_9 | Value::_8 | Value::_7) => 0,
         }
     }
     pub const fn color(&self) -> Color {
         self.color
     }
+    /// Unique index of the card, between 0 and 31.
+    pub const fn index(&self) -> u32 {
+        self.color as u32 * 8 + self.value as u32
+    }
+    /// Card of unique index `index`, `None` past 31.
+    pub const fn from_index(index: u32) -> Option<Self> {
+        if index >= 32 {
+            return None;
+        }
+        Some(Self {
+            color: COLORS[(index / 8) as usize],
+            value: VALUES[(index % 8) as usize],
+        })
+    }
     pub const fn value(&self) -> Value {
         self.value
     }
-    pub fn master(self, arg: Self, trump_color: Color) -> bool {
-        match (self, arg) {
-            (card1, card2) if card1.color == trump_color && card2.color != trump_color => true,
-            (card1, card2) if card1.color != trump_color && card2.color == trump_color => false,
-            (card1, card2) => {
-                card1.color != card2.color || card1.power(trump_color) > card2.power(trump_color)
-            }
+    /// Does this card, already on the table, stay master when `arg` is played after it ?
+    pub fn master(self, arg: Self, trump: Trump) -> bool {
+        match (trump.is_trump(self.color), trump.is_trump(arg.color)) {
+            (true, false) => true,
+            (false, true) => false,
+            _ => self.color != arg.color || self.power(trump) > arg.power(trump),
         }
     }
-    pub fn power(self, trump_color: Color) -> u8 {
-        match self.value {
-            Value::Jack => {
-                if self.color == trump_color {
-                    20
-                } else {
-                    2
-                }
+    /// Rank of the card inside its color : J 9 A 10 K Q 8 7 for trumps, A 10 K Q J 9 8 7 otherwise.
+    pub fn power(self, trump: Trump) -> u8 {
+        if trump.is_trump(self.color) {
+            match self.value {
+                Value::Jack => 7,
+                Value::_9 => 6,
+                Value::As => 5,
+                Value::_10 => 4,
+                Value::King => 3,
+                Value::Queen => 2,
+                Value::_8 => 1,
+                Value::_7 => 0,
             }
-            Value::_9 => {
-                if self.color == trump_color {
-                    14
-                } else {
-                    2
-                }
+        } else {
+            match self.value {
+                Value::As => 7,
+                Value::_10 => 6,
+                Value::King => 5,
+                Value::Queen => 4,
+                Value::Jack => 3,
+                Value::_9 => 2,
+                Value::_8 => 1,
+                Value::_7 => 0,
             }
-            Value::As => 11,
-            Value::_10 => 10,
-            Value::King => 4,
-            Value::Queen => 3,
-            Value::_8 => 1,
-            Value::_7 => 0,
         }
     }
 }
@@ -197,9 +316,11 @@ fn card_tests() {
         color: Color::Club,
         value: Value::_8,
     };
-    assert!(!spade_10.master(diamond_7, Color::Diamond));
-    assert!(spade_10.master(spade_7, Color::Club));
-    assert!(club_8.master(diamond_7, Color::Heart));
+    assert!(!spade_10.master(diamond_7, Trump::Color(Color::Diamond)));
+    assert!(spade_10.master(spade_7, Trump::Color(Color::Club)));
+    assert!(club_8.master(diamond_7, Trump::Color(Color::Heart)));
+    assert!(club_8.master(diamond_7, Trump::AllTrump));
+    assert!(club_8.master(diamond_7, Trump::NoTrump));
 
     let heart_10 = Card {
         color: Color::Heart,
@@ -209,5 +330,17 @@ fn card_tests() {
         color: Color::Heart,
         value: Value::Jack,
     };
-    assert!(heart_j.master(heart_10, Color::Heart));
+    assert!(heart_j.master(heart_10, Trump::Color(Color::Heart)));
+    assert!(heart_j.master(heart_10, Trump::AllTrump));
+    assert!(!heart_j.master(heart_10, Trump::NoTrump));
+
+    for card in Card::all() {
+        assert_eq!(Card::from_index(card.index()), Some(card));
+    }
+    assert_eq!(Card::from_index(32), None);
+
+    for trump in Trump::all() {
+        let total: u64 = Card::all().map(|card| card.points(trump)).sum();
+        assert_eq!(total, 152, "{trump}");
+    }
 }
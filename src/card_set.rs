@@ -0,0 +1,264 @@
+use crate::card::{Card, Color, Trump};
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not, Sub, SubAssign};
+
+/// Values ranked above each value of a trump color (J 9 A 10 K Q 8 7), as bits by value index.
+const ABOVE_TRUMP: [u32; 8] = [0xFE, 0xFC, 0x08, 0x00, 0xEC, 0xCC, 0x8C, 0x0C];
+
+/// Set of cards, one bit per card at its [`Card::index`] : set operations and lookups are
+/// single instructions and never allocate.
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[must_use]
+pub struct CardSet(u32);
+
+impl CardSet {
+    pub const EMPTY: Self = Self(0);
+    /// The 32 cards of the game.
+    pub const ALL: Self = Self(u32::MAX);
+
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+    pub const fn single(card: Card) -> Self {
+        Self(1 << card.index())
+    }
+    /// The 8 cards of `color`.
+    pub const fn color(color: Color) -> Self {
+        Self(0xFF << (color as u32 * 8))
+    }
+    /// Every trump card of `trump`.
+    pub const fn trumps(trump: Trump) -> Self {
+        match trump {
+            Trump::Color(color) => Self::color(color),
+            Trump::NoTrump => Self::EMPTY,
+            Trump::AllTrump => Self::ALL,
+        }
+    }
+    /// Cards of the color of `card` ranked above it when `trump` is played.
+    pub const fn above(card: Card, trump: Trump) -> Self {
+        let value = card.value() as u32;
+        let is_trump = match trump {
+            Trump::Color(color) => color as u32 == card.color() as u32,
+            Trump::NoTrump => false,
+            Trump::AllTrump => true,
+        };
+        let above = if is_trump {
+            ABOVE_TRUMP[value as usize]
+        } else {
+            (0xFF << (value + 1)) & 0xFF
+        };
+        Self(above << (card.color() as u32 * 8))
+    }
+    /// Cards taking the trick from `master` when played after it.
+    pub const fn beating(master: Card, trump: Trump) -> Self {
+        let above = Self::above(master, trump);
+        if Self::trumps(trump).contains(master) {
+            above
+        } else {
+            above.union(Self::trumps(trump))
+        }
+    }
+
+    pub const fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+    pub const fn contains(self, card: Card) -> bool {
+        self.0 & Self::single(card).0 != 0
+    }
+    /// Adds `card`, `false` if it was already there.
+    pub const fn insert(&mut self, card: Card) -> bool {
+        let absent = !self.contains(card);
+        self.0 |= Self::single(card).0;
+        absent
+    }
+    /// Removes `card`, `false` if it was not there.
+    pub const fn remove(&mut self, card: Card) -> bool {
+        let present = self.contains(card);
+        self.0 &= !Self::single(card).0;
+        present
+    }
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+    pub const fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+    /// Cards of this set in `color`.
+    pub const fn of_color(self, color: Color) -> Self {
+        self.intersection(Self::color(color))
+    }
+    /// Strongest card of the set when `trump` is played, trumps first.
+    pub fn highest(self, trump: Trump) -> Option<Card> {
+        self.into_iter()
+            .max_by_key(|card| (Self::trumps(trump).contains(*card), card.power(trump)))
+    }
+    /// Weakest card of the set when `trump` is played, trumps last.
+    pub fn lowest(self, trump: Trump) -> Option<Card> {
+        self.into_iter()
+            .min_by_key(|card| (Self::trumps(trump).contains(*card), card.power(trump)))
+    }
+}
+
+/// Cards of a [`CardSet`], by index.
+#[derive(Clone, Debug)]
+pub struct Cards(u32);
+
+impl Iterator for Cards {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Card::from_index(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Cards {}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = Cards;
+
+    fn into_iter(self) -> Cards {
+        Cards(self.0)
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(cards: I) -> Self {
+        let mut set = Self::EMPTY;
+        for card in cards {
+            set.insert(card);
+        }
+        set
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+impl BitOrAssign for CardSet {
+    fn bitor_assign(&mut self, other: Self) {
+        *self = self.union(other);
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        self.intersection(other)
+    }
+}
+
+impl BitAndAssign for CardSet {
+    fn bitand_assign(&mut self, other: Self) {
+        *self = self.intersection(other);
+    }
+}
+
+impl Sub for CardSet {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.difference(other)
+    }
+}
+
+impl SubAssign for CardSet {
+    fn sub_assign(&mut self, other: Self) {
+        *self = self.difference(other);
+    }
+}
+
+impl Not for CardSet {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(!self.0)
+    }
+}
+
+impl fmt::Display for CardSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (rank, card) in self.into_iter().enumerate() {
+            if rank > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{card}")?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn card_set_tests() {
+    use crate::card::Value;
+    use std::str::FromStr;
+
+    let card = |card: &str| Card::from_str(card).unwrap_or_else(|_| panic!("bad card {card}"));
+    assert_eq!(CardSet::ALL.len(), 32);
+    assert_eq!(
+        CardSet::ALL.into_iter().collect::<Vec<_>>(),
+        Card::all().collect::<Vec<_>>()
+    );
+    for color in [Color::Heart, Color::Spade, Color::Diamond, Color::Club] {
+        assert!(CardSet::color(color)
+            .into_iter()
+            .all(|card| card.color() == color));
+    }
+
+    let mut set: CardSet = ["♥J", "♥9", "♠11"].into_iter().map(card).collect();
+    assert!(!set.insert(card("♥J")));
+    assert!(set.remove(card("♠11")));
+    assert!(!set.remove(card("♠11")));
+    assert_eq!(
+        set,
+        CardSet::single(card("♥J")) | CardSet::single(card("♥9"))
+    );
+    assert_eq!((set - CardSet::color(Color::Heart)).len(), 0);
+    assert_eq!((!set).len(), 30);
+
+    // The ranks agree with the power of the cards, whatever the trump.
+    for trump in Trump::all() {
+        for master in Card::all() {
+            for other in Card::all().filter(|other| *other != master) {
+                assert_eq!(
+                    CardSet::beating(master, trump).contains(other),
+                    !master.master(other, trump),
+                    "{master} {other} {trump}"
+                );
+            }
+        }
+    }
+    let hearts = Trump::Color(Color::Heart);
+    assert_eq!(set.highest(hearts), Some(card("♥J")));
+    let mixed: CardSet = ["♥7", "♠11", "♦10"].into_iter().map(card).collect();
+    assert_eq!(mixed.highest(hearts), Some(card("♥7")));
+    assert_eq!(
+        mixed.lowest(hearts).map(|card| card.value()),
+        Some(Value::_10)
+    );
+}
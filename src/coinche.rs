@@ -1,102 +1,348 @@
-// use strum::IntoEnumIterator;
-
-// #[derive(Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd, EnumIter)]
-// pub enum Contract {
-//     Pass = 0,
-//     _80 = 80,
-//     _90 = 90,
-//     _100 = 100,
-//     _110 = 110,
-//     _120 = 120,
-//     _130 = 130,
-//     _140 = 140,
-//     _150 = 150,
-//     _160 = 160,
-//     _170 = 170,
-//     _180 = 180,
-//     Capot = 250,
-//     CapotBelote = 270,
-//     Generale = 500, // one player announce he will get all cards
-// }
-
-// #[derive(Eq, PartialEq, Clone, Debug)]
-// pub enum Type {
-//     Color(Color),
-//     SansAtout,
-//     ToutAtout,
-// }
-
-// #[derive(Eq, PartialEq, Clone, Debug)]
-// pub enum Interruption {
-//     Coinche,
-//     Surcoinche,
-// }
-
-// impl fmt::Display for Contract {
-//     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-//         match self {
-//             Self::Pass => write!(f, "Passe."),
-//             Self::Capot => write!(f, "Capot."),
-//             Self::CapotBelote => write!(f, "Capot beloté."),
-//             Self::Generale => write!(f, "Generale."),
-//             _ => write!(f, "{}", *self as usize),
-//         }
-//     }
-// }
-
-// pub contract: Option<Contract>,
-// pub interruption: Option<Interruption>,
-// pub team: Option<Team>,
-
-// pub fn bidding(&mut self) -> Result<(), Error> {
-//     let mut contracts: Vec<Contract> = Contract::iter().collect();
-
-//     for p in self.players.iter_mut() {
-//         if self.auto && contracts.len() == 1 {
-//             p.contract = Some(Contract::Pass);
-//             println!("Auto pass");
-//             continue;
-//         }
-
-//         p.contract = if self.random {
-//             Some(contracts[rand::thread_rng().gen_range(0..contracts.len())])
-//         } else {
-//             loop {
-//                 println!("{} must play : {}", &p, &p.hand);
-//                 println!("Choose a contract, possibilities :");
-//                 for (i, c) in contracts.iter().enumerate() {
-//                     println!("\t{} : press {}", c, i);
-//                 }
-//                 let contract_index = read_index();
-//                 if contract_index < contracts.len() {
-//                     break Some(contracts[contract_index]);
-//                 } else {
-//                     println!("Error, please retry");
-//                 }
-//             }
-//         };
-
-//         contracts = match p.contract {
-//             Some(Contract::Pass) => {
-//                 println!("Pass");
-//                 p.contract = Some(Contract::Pass);
-//                 contracts
-//             }
-//             Some(contract) => {
-//                 println!("Chosen contract: {}", contract);
-//                 p.contract = Some(contract);
-//                 Contract::iter()
-//                     .filter(|other_contract| {
-//                         other_contract == &Contract::Pass
-//                             || *other_contract as usize > contract as usize
-//                     })
-//                     .collect()
-//             }
-//             _ => {
-//                 println!("A contract must be available for everyone!");
-//                 return Err(BeloteErrorKind::InvalidCase.into());
-//             }
-//         };
-//     }
-//     Ok(())
-// }
+use crate::bidding::{Bid, PlayOrNext};
+use crate::card::Trump;
+use crate::constants::MAX_PLAYERS;
+use crate::engine::{Action, Decision, Step};
+use crate::errors::BeloteErrorKind;
+use crate::event::GameEvent;
+use crate::game::Game;
+use crate::hand::Hand;
+use crate::hands::Hands;
+use crate::initial::Initial;
+use crate::playing::Playing;
+use crate::position::Position;
+use derive_more::{Deref, DerefMut};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+use tracing::info;
+
+/// Points a team commits to make during the auction, the level of its contract.
+#[derive(
+    Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd, EnumIter, Hash, Serialize, Deserialize,
+)]
+pub enum Level {
+    _80 = 80,
+    _90 = 90,
+    _100 = 100,
+    _110 = 110,
+    _120 = 120,
+    _130 = 130,
+    _140 = 140,
+    _150 = 150,
+    _160 = 160,
+    _170 = 170,
+    _180 = 180,
+    /// The taker team will win every trick.
+    Capot = 250,
+    /// The taker alone will win every trick.
+    Generale = 500,
+}
+
+impl Level {
+    pub const fn value(self) -> u64 {
+        self as u64
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Capot => write!(f, "Capot"),
+            Self::Generale => write!(f, "Generale"),
+            _ => write!(f, "{}", self.value()),
+        }
+    }
+}
+
+/// Doubling of the contract by the defense, then redoubling by the taker team.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Interruption {
+    Coinche,
+    Surcoinche,
+}
+
+impl Interruption {
+    pub const fn multiplier(self) -> u64 {
+        match self {
+            Self::Coinche => 2,
+            Self::Surcoinche => 4,
+        }
+    }
+}
+
+impl fmt::Display for Interruption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Coinche => write!(f, "Coinche"),
+            Self::Surcoinche => write!(f, "Surcoinche"),
+        }
+    }
+}
+
+/// Contract won at the end of the auction.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Taken {
+    taker: Position,
+    #[serde(alias = "contract")]
+    level: Level,
+    trump: Trump,
+    interruption: Option<Interruption>,
+}
+
+impl Taken {
+    pub const fn taker(&self) -> Position {
+        self.taker
+    }
+    pub const fn level(&self) -> Level {
+        self.level
+    }
+    pub const fn trump(&self) -> Trump {
+        self.trump
+    }
+    pub const fn interruption(&self) -> Option<Interruption> {
+        self.interruption
+    }
+    pub const fn multiplier(&self) -> u64 {
+        match self.interruption {
+            Some(interruption) => interruption.multiplier(),
+            None => 1,
+        }
+    }
+}
+
+/// Bids made so far during an auction.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Bids {
+    best: Option<(Position, Level, Trump)>,
+    interruption: Option<Interruption>,
+    passes: usize,
+}
+
+impl Bids {
+    /// Highest announce so far, with the position which made it.
+    pub const fn best(&self) -> Option<(Position, Level, Trump)> {
+        self.best
+    }
+
+    pub const fn interruption(&self) -> Option<Interruption> {
+        self.interruption
+    }
+
+    /// Bids `position` is allowed to make : announces must go up, only the opponents of
+    /// the best announce can coinche it, only its team can then surcoinche.
+    pub fn legal(&self, position: Position) -> Vec<Bid> {
+        let mut bids = vec![Bid::Pass];
+        match (self.best, self.interruption) {
+            (Some((bidder, _, _)), Some(Interruption::Coinche)) => {
+                if bidder.team() == position.team() {
+                    bids.push(Bid::Surcoinche);
+                }
+            }
+            (best, None) => {
+                for level in
+                    Level::iter().filter(|level| best.is_none_or(|(_, best, _)| *level > best))
+                {
+                    for trump in Trump::all() {
+                        bids.push(Bid::Announce { level, trump });
+                    }
+                }
+                if let Some((bidder, _, _)) = best {
+                    if bidder.team() != position.team() {
+                        bids.push(Bid::Coinche);
+                    }
+                }
+            }
+            (None, Some(_)) | (_, Some(Interruption::Surcoinche)) => {}
+        }
+        bids
+    }
+
+    const fn apply(&mut self, position: Position, bid: Bid) {
+        match bid {
+            Bid::Announce { level, trump } => {
+                self.best = Some((position, level, trump));
+                self.passes = 0;
+            }
+            Bid::Coinche => {
+                self.interruption = Some(Interruption::Coinche);
+                self.passes = 0;
+            }
+            Bid::Surcoinche => self.interruption = Some(Interruption::Surcoinche),
+            Bid::Pass | Bid::Take | Bid::Color(_) => self.passes += 1,
+        }
+    }
+
+    /// The auction stops after a surcoinche, or when the 3 other players passed after the
+    /// last announce or coinche. Without any announce, everybody must pass.
+    pub const fn is_over(&self) -> bool {
+        match (self.best, self.interruption) {
+            (_, Some(Interruption::Surcoinche)) => true,
+            (None, _) => self.passes >= 4,
+            (Some(_), _) => self.passes >= 3,
+        }
+    }
+
+    pub fn taken(&self) -> Option<Taken> {
+        self.best.map(|(taker, level, trump)| Taken {
+            taker,
+            level,
+            trump,
+            interruption: self.interruption,
+        })
+    }
+}
+
+/// Phase where players bid on contracts, after all 8 cards have been dealt.
+#[derive(Deref, DerefMut)]
+pub struct Auction {
+    hands: Hands,
+    bids: Bids,
+    /// Bids made so far, seats bidding in order until the auction is over.
+    turns: usize,
+    #[deref]
+    #[deref_mut]
+    initial: Initial,
+}
+
+impl Auction {
+    pub fn new(hands: Hands, initial: Initial) -> Self {
+        Self {
+            hands,
+            bids: Bids::default(),
+            turns: 0,
+            initial,
+        }
+    }
+
+    pub const fn into(self) -> Initial {
+        self.initial
+    }
+
+    pub fn hand(&self, position: Position) -> Hand {
+        self.hands[position]
+    }
+
+    pub const fn bids(&self) -> &Bids {
+        &self.bids
+    }
+}
+
+impl Game<Auction> {
+    /// Runs the auction until it is over, then starts playing the contract or redistributes.
+    pub fn playing_game_or_redistribute(mut self) -> Result<PlayOrNext, BeloteErrorKind> {
+        loop {
+            let decision = self.pending_decision();
+            let Some(action) = self.decide(&decision) else {
+                info!("Interrupted.");
+                return Ok(PlayOrNext::Interrupted);
+            };
+            self = match self.apply(action)? {
+                Step::Pending(game) => game,
+                Step::Over(play_or_next) => return Ok(play_or_next),
+            };
+        }
+    }
+
+    /// Next seat to bid, around the table until the auction is over.
+    pub fn pending_decision(&self) -> Decision {
+        let position = self.order()[self.turns % MAX_PLAYERS];
+        Decision::Bid {
+            position,
+            hand: self.hand(position),
+            bids: self.bids.clone(),
+        }
+    }
+
+    /// Plays the bid of the seat to decide, then starts playing the contract or redistributes
+    /// once the auction is over.
+    #[allow(clippy::needless_pass_by_value)] // Every phase takes its action the same way.
+    pub fn apply(mut self, action: Action) -> Result<Step<Auction, PlayOrNext>, BeloteErrorKind> {
+        let decision = self.pending_decision();
+        decision.check(&action)?;
+        let position = decision.position();
+        if let Action::Bid(bid) = action {
+            info!("{position} bids {bid}");
+            self.bids.apply(position, bid);
+            self.turns += 1;
+            self.notify(GameEvent::BidMade { position, bid });
+        }
+        if !self.bids.is_over() {
+            return Ok(Step::Pending(self));
+        }
+
+        let Some(taken) = self.bids.taken() else {
+            for position in self.order() {
+                for card in self.hands[position] {
+                    self.stack_mut().append_card(card)?;
+                }
+            }
+            return Ok(Step::Over(PlayOrNext::NextGame(
+                self.map(|auction| auction.into().next()),
+            )));
+        };
+
+        info!(
+            "{} takes {} at {}",
+            taken.taker(),
+            taken.level(),
+            taken.trump()
+        );
+        self.notify(GameEvent::TrumpChosen {
+            taker: taken.taker(),
+            trump: taken.trump(),
+        });
+        Ok(Step::Over(PlayOrNext::PlayGame(self.map(|auction| {
+            Playing::new(
+                taken.taker(),
+                auction.hands,
+                taken.trump(),
+                Some(taken),
+                auction.initial,
+            )
+        }))))
+    }
+}
+
+#[test]
+fn coinche_tests() {
+    use crate::card::Color;
+
+    let mut bids = Bids::default();
+    assert_eq!(bids.legal(Position::North).len(), 1 + 13 * 6);
+    bids.apply(Position::North, Bid::Pass);
+    bids.apply(
+        Position::East,
+        Bid::Announce {
+            level: Level::_100,
+            trump: Trump::Color(Color::Heart),
+        },
+    );
+    assert!(!bids.legal(Position::South).contains(&Bid::Announce {
+        level: Level::_90,
+        trump: Trump::Color(Color::Spade)
+    }));
+    assert!(bids.legal(Position::South).contains(&Bid::Coinche));
+    assert!(!bids.legal(Position::West).contains(&Bid::Coinche));
+    bids.apply(Position::South, Bid::Coinche);
+    assert_eq!(bids.legal(Position::North), vec![Bid::Pass]);
+    assert_eq!(bids.legal(Position::West), vec![Bid::Pass, Bid::Surcoinche]);
+    bids.apply(Position::West, Bid::Pass);
+    bids.apply(Position::North, Bid::Pass);
+    assert!(!bids.is_over());
+    bids.apply(Position::East, Bid::Pass);
+    assert!(bids.is_over());
+    let Some(taken) = bids.taken() else {
+        panic!("East should have taken");
+    };
+    assert_eq!(taken.taker(), Position::East);
+    assert_eq!(taken.multiplier(), 2);
+
+    let mut nobody = Bids::default();
+    for position in Position::iter() {
+        assert!(!nobody.is_over());
+        nobody.apply(position, Bid::Pass);
+    }
+    assert!(nobody.is_over());
+    assert!(nobody.taken().is_none());
+}
@@ -1,4 +1,4 @@
-use crate::card::Color;
+use crate::card::{Color, Trump};
 use crate::errors::BeloteErrorKind;
 use core::fmt::Display;
 use derive_more::Debug;
@@ -14,16 +14,20 @@ pub enum Contract {
     Spade,
     Diamond,
     Club,
+    NoTrump,
+    AllTrump,
     Pass,
 }
 
 impl Contract {
-    pub const fn color(&self) -> Option<Color> {
+    pub const fn trump(&self) -> Option<Trump> {
         match self {
-            Self::Club => Some(Color::Club),
-            Self::Diamond => Some(Color::Diamond),
-            Self::Heart => Some(Color::Heart),
-            Self::Spade => Some(Color::Spade),
+            Self::Club => Some(Trump::Color(Color::Club)),
+            Self::Diamond => Some(Trump::Color(Color::Diamond)),
+            Self::Heart => Some(Trump::Color(Color::Heart)),
+            Self::Spade => Some(Trump::Color(Color::Spade)),
+            Self::NoTrump => Some(Trump::NoTrump),
+            Self::AllTrump => Some(Trump::AllTrump),
             Self::Pass => None,
         }
     }
@@ -31,10 +35,10 @@ impl Contract {
 
 impl Display for Contract {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.color() {
+        match self.trump() {
             None => write!(f, "{PASS}"),
-            Some(color) => {
-                write!(f, "{color}")
+            Some(trump) => {
+                write!(f, "{trump}")
             }
         }
     }
@@ -47,11 +51,13 @@ impl FromStr for Contract {
         if s == PASS {
             Ok(Self::Pass)
         } else {
-            Ok(match Color::from_str(s)? {
-                Color::Club => Self::Club,
-                Color::Diamond => Self::Diamond,
-                Color::Heart => Self::Heart,
-                Color::Spade => Self::Spade,
+            Ok(match Trump::from_str(s)? {
+                Trump::Color(Color::Club) => Self::Club,
+                Trump::Color(Color::Diamond) => Self::Diamond,
+                Trump::Color(Color::Heart) => Self::Heart,
+                Trump::Color(Color::Spade) => Self::Spade,
+                Trump::NoTrump => Self::NoTrump,
+                Trump::AllTrump => Self::AllTrump,
             })
         }
     }
@@ -1,11 +1,14 @@
 use crate::bidding::Bidding;
+use crate::coinche::Auction;
 use crate::errors::BeloteErrorKind;
+use crate::event::GameEvent;
 use crate::game::Game;
 use crate::hands::Hands;
 use crate::initial::Initial;
 use derive_more::{Constructor, Deref, DerefMut};
 use tracing::info;
 
+/// Phase after the first 5 cards of each player have been dealt.
 #[derive(Constructor, Deref, DerefMut)]
 pub struct Distribution {
     hands: Hands,
@@ -15,13 +18,27 @@ pub struct Distribution {
 }
 
 impl Game<Distribution> {
+    /// Returns the next card of the stack, which is proposed to the players.
     pub fn bidding(mut self) -> Result<Game<Bidding>, BeloteErrorKind> {
         let card_returned = self.stack_mut().give_card()?;
         info!("Card returned: {card_returned}");
-        Ok(Game::new(
-            self.players(),
-            self.points(),
-            Bidding::new(card_returned, self.hands, self.into().initial),
-        ))
+        self.notify(GameEvent::CardReturned {
+            card: card_returned,
+        });
+        Ok(self.map(|distribution| {
+            Bidding::new(card_returned, distribution.hands, distribution.initial)
+        }))
+    }
+
+    /// Deals the last 3 cards of every player, without returning any card, before a coinche auction.
+    pub fn auction(mut self) -> Result<Game<Auction>, BeloteErrorKind> {
+        for position in self.order() {
+            for _ in 0..3 {
+                let card = self.stack_mut().give_card()?;
+                self.hands[position].take(card)?;
+            }
+            info!("{position} : {}", self.hands[position]);
+        }
+        Ok(self.map(|distribution| Auction::new(distribution.hands, distribution.initial)))
     }
 }
@@ -0,0 +1,407 @@
+use crate::agent::Agent;
+use crate::announce::Announce;
+use crate::bidding::{Bid, Bidding, PlayOrNext};
+use crate::card::{Card, Trump};
+use crate::coinche::{Auction, Bids};
+use crate::errors::BeloteErrorKind;
+use crate::game::Game;
+use crate::hand::Hand;
+use crate::initial::Initial;
+use crate::player::Choices;
+use crate::playing::Playing;
+use crate::points::Points;
+use crate::position::Position;
+use crate::trick::Trick;
+use crate::variant::Variant;
+use crate::view::PlayerView;
+use rand::RngCore;
+
+/// Decision a seat must take for the deal to go on, with its legal options.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Decision {
+    /// First bidding turn : take the returned card or pass.
+    Take {
+        position: Position,
+        hand: Hand,
+        card_returned: Card,
+    },
+    /// Second bidding turn : choose one of `trumps` or pass.
+    ChooseColor {
+        position: Position,
+        hand: Hand,
+        card_returned: Card,
+        trumps: Vec<Trump>,
+    },
+    /// Coinche auction : make one of `bids.legal(position)`.
+    Bid {
+        position: Position,
+        hand: Hand,
+        bids: Bids,
+    },
+    /// First trick : declare some of `announces` before playing.
+    Declare {
+        position: Position,
+        hand: Hand,
+        announces: Vec<Announce>,
+    },
+    /// Announce belote or not while playing `card`, the first of the trump king and queen.
+    DeclareBelote {
+        position: Position,
+        hand: Hand,
+        card: Card,
+    },
+    /// Play one of the legal cards of `choices`.
+    PlayCard {
+        position: Position,
+        hand: Hand,
+        choices: Choices,
+    },
+}
+
+/// Answer to a [`Decision`] of the same kind.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Action {
+    Take(bool),
+    /// `None` is a pass.
+    ChooseColor(Option<Trump>),
+    Bid(Bid),
+    Declare(Vec<Announce>),
+    DeclareBelote(bool),
+    PlayCard(Card),
+}
+
+impl Decision {
+    /// Seat which must decide.
+    pub const fn position(&self) -> Position {
+        match self {
+            Self::Take { position, .. }
+            | Self::ChooseColor { position, .. }
+            | Self::Bid { position, .. }
+            | Self::Declare { position, .. }
+            | Self::DeclareBelote { position, .. }
+            | Self::PlayCard { position, .. } => *position,
+        }
+    }
+
+    pub const fn hand(&self) -> Hand {
+        match self {
+            Self::Take { hand, .. }
+            | Self::ChooseColor { hand, .. }
+            | Self::Bid { hand, .. }
+            | Self::Declare { hand, .. }
+            | Self::DeclareBelote { hand, .. }
+            | Self::PlayCard { hand, .. } => *hand,
+        }
+    }
+
+    /// Is `action` a legal answer to this decision ?
+    pub fn check(&self, action: &Action) -> Result<(), BeloteErrorKind> {
+        let position = self.position();
+        match (self, action) {
+            (Self::Take { .. }, Action::Take(_))
+            | (Self::ChooseColor { .. }, Action::ChooseColor(None))
+            | (Self::DeclareBelote { .. }, Action::DeclareBelote(_)) => Ok(()),
+            (Self::ChooseColor { trumps, .. }, Action::ChooseColor(Some(trump))) => {
+                if trumps.contains(trump) {
+                    Ok(())
+                } else {
+                    Err(BeloteErrorKind::InvalidCase(format!(
+                        "{position} cannot choose {trump}"
+                    )))
+                }
+            }
+            (Self::Bid { bids, .. }, Action::Bid(bid)) => {
+                if bids.legal(position).contains(bid) {
+                    Ok(())
+                } else {
+                    Err(BeloteErrorKind::InvalidCase(format!(
+                        "{position} cannot bid {bid}"
+                    )))
+                }
+            }
+            (Self::Declare { announces, .. }, Action::Declare(declared)) => {
+                for (index, announce) in declared.iter().enumerate() {
+                    if !announces.contains(announce) || declared[..index].contains(announce) {
+                        return Err(BeloteErrorKind::InvalidCase(format!(
+                            "{position} cannot declare {announce}"
+                        )));
+                    }
+                }
+                Ok(())
+            }
+            (Self::PlayCard { choices, .. }, Action::PlayCard(card)) => {
+                if choices.contains(card) {
+                    Ok(())
+                } else {
+                    Err(BeloteErrorKind::InvalidCard(card.to_string()))
+                }
+            }
+            _ => Err(BeloteErrorKind::InvalidCase(format!(
+                "{action:?} does not answer what {position} must decide"
+            ))),
+        }
+    }
+
+    /// Asks `agent`, seeing `view`, to take this decision, `None` meaning it interrupted the
+    /// game.
+    pub fn ask(
+        &self,
+        agent: &mut dyn Agent,
+        view: &PlayerView,
+        rng: &mut dyn RngCore,
+    ) -> Option<Action> {
+        Some(match self {
+            Self::Take { card_returned, .. } => {
+                Action::Take(agent.take(view, *card_returned, rng)?)
+            }
+            Self::ChooseColor { card_returned, .. } => {
+                Action::ChooseColor(agent.choose_color(view, *card_returned, rng)?)
+            }
+            Self::Bid { bids, .. } => Action::Bid(agent.bid(view, bids, rng)?),
+            Self::Declare { announces, .. } => {
+                Action::Declare(agent.declare(view, announces, rng)?)
+            }
+            Self::DeclareBelote { card, .. } => {
+                Action::DeclareBelote(agent.declare_belote(view, *card, rng)?)
+            }
+            Self::PlayCard { choices, .. } => {
+                let legal: Vec<Card> = choices.legal().into_iter().collect();
+                Action::PlayCard(agent.choose_card(view, &legal, rng)?)
+            }
+        })
+    }
+}
+
+/// A phase after an action : still waiting for decisions, or over with what follows it.
+#[allow(clippy::large_enum_variant)]
+pub enum Step<State, Next> {
+    Pending(Game<State>),
+    Over(Next),
+}
+
+/// Phase of the deal driven by an [`Engine`].
+#[allow(clippy::large_enum_variant)]
+enum Phase {
+    Bidding(Game<Bidding>),
+    Auction(Game<Auction>),
+    Playing(Game<Playing>),
+    Over(Game<Initial>),
+}
+
+/// A deal advanced one action at a time, for frontends which prompt their humans themselves.
+///
+/// The engine tells which [`Decision`] it waits for and never asks an agent by itself : the
+/// frontend answers with [`Engine::apply`], or lets the agent of the seat decide with
+/// [`Engine::ask`]. An action which is not legal is refused, the deal staying where it was.
+/// Should the game itself fail past that check, the deal is lost : the engine is then neither
+/// waiting for a decision nor over, and refuses any further action.
+pub struct Engine {
+    phase: Option<Phase>,
+    pending: Option<Decision>,
+}
+
+impl Engine {
+    /// Cuts and deals the stack of `game`, then waits for the first bid.
+    pub fn new(game: Game<Initial>) -> Result<Self, BeloteErrorKind> {
+        let distribution = game.distribute()?;
+        let phase = match distribution.variant() {
+            Variant::Belote => Phase::Bidding(distribution.bidding()?),
+            Variant::Coinche => Phase::Auction(distribution.auction()?),
+        };
+        Ok(Self {
+            pending: Self::decision(&phase)?,
+            phase: Some(phase),
+        })
+    }
+
+    /// Decision the deal waits for, `None` once it is over.
+    pub const fn pending_decision(&self) -> Option<&Decision> {
+        self.pending.as_ref()
+    }
+
+    pub const fn is_over(&self) -> bool {
+        matches!(self.phase, Some(Phase::Over(_)))
+    }
+
+    /// Plays `action` for the seat which must decide, then goes on until the next decision.
+    pub fn apply(&mut self, action: Action) -> Result<(), BeloteErrorKind> {
+        let Some(decision) = &self.pending else {
+            return Err(BeloteErrorKind::InvalidCase(if self.is_over() {
+                "the deal is over".to_string()
+            } else {
+                "the deal was lost after an error".to_string()
+            }));
+        };
+        // Refused before the phase is taken, a wrong action leaves the deal untouched.
+        decision.check(&action)?;
+        self.pending = None;
+        let phase = match self.phase.take() {
+            Some(Phase::Bidding(game)) => match game.apply(action)? {
+                Step::Pending(game) => Phase::Bidding(game),
+                Step::Over(next) => Self::after_bidding(next)?,
+            },
+            Some(Phase::Auction(game)) => match game.apply(action)? {
+                Step::Pending(game) => Phase::Auction(game),
+                Step::Over(next) => Self::after_bidding(next)?,
+            },
+            Some(Phase::Playing(game)) => match game.apply(action)? {
+                Step::Pending(game) => Phase::Playing(game),
+                Step::Over(next) => Phase::Over(next),
+            },
+            Some(Phase::Over(_)) | None => {
+                return Err(BeloteErrorKind::InvalidCase("the deal is over".to_string()))
+            }
+        };
+        let pending = Self::decision(&phase);
+        self.phase = Some(phase);
+        self.pending = pending?;
+        Ok(())
+    }
+
+    /// Asks the agent of the seat which must decide, `None` when it interrupted the game or the
+    /// deal is over.
+    pub fn ask(&mut self) -> Option<Action> {
+        let decision = self.pending.as_ref()?;
+        match self.phase.as_mut()? {
+            Phase::Bidding(game) => game.decide(decision),
+            Phase::Auction(game) => game.decide(decision),
+            Phase::Playing(game) => game.decide(decision),
+            Phase::Over(_) => None,
+        }
+    }
+
+    /// Is a human sitting at `position` ?
+    pub fn is_human(&self, position: Position) -> bool {
+        let players = match &self.phase {
+            Some(Phase::Bidding(game)) => game.players(),
+            Some(Phase::Auction(game)) => game.players(),
+            Some(Phase::Playing(game)) => game.players(),
+            Some(Phase::Over(game)) => game.players(),
+            None => return false,
+        };
+        players[position].agent().is_human()
+    }
+
+    /// Last trick won in the deal being played, `None` before the first one and once it is over.
+    pub fn last_trick(&self) -> Option<&Trick> {
+        match &self.phase {
+            Some(Phase::Playing(game)) => game.last_trick(),
+            _ => None,
+        }
+    }
+
+    /// Points of the game, the deal included once it is scored.
+    pub fn points(&self) -> Points {
+        match &self.phase {
+            Some(Phase::Bidding(game)) => game.points(),
+            Some(Phase::Auction(game)) => game.points(),
+            Some(Phase::Playing(game)) => game.points(),
+            Some(Phase::Over(game)) => game.points(),
+            None => Points::default(),
+        }
+    }
+
+    /// Game ready for the next deal, `None` while this one is not over.
+    pub fn into_game(self) -> Option<Game<Initial>> {
+        match self.phase {
+            Some(Phase::Over(game)) => Some(game),
+            _ => None,
+        }
+    }
+
+    fn after_bidding(next: PlayOrNext) -> Result<Phase, BeloteErrorKind> {
+        match next {
+            PlayOrNext::NextGame(game) => Ok(Phase::Over(game)),
+            PlayOrNext::PlayGame(game) => Ok(Phase::Playing(game)),
+            PlayOrNext::Interrupted => Err(BeloteErrorKind::InvalidCase(
+                "the bidding was interrupted".to_string(),
+            )),
+        }
+    }
+
+    fn decision(phase: &Phase) -> Result<Option<Decision>, BeloteErrorKind> {
+        Ok(match phase {
+            Phase::Bidding(game) => Some(game.pending_decision()),
+            Phase::Auction(game) => Some(game.pending_decision()),
+            Phase::Playing(game) => Some(game.pending_decision()?),
+            Phase::Over(_) => None,
+        })
+    }
+}
+
+#[test]
+fn engine_tests() {
+    use crate::agent::Random;
+    use crate::card_set::CardSet;
+    use crate::order::Order;
+    use crate::player::Player;
+    use crate::players::Players;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let game = |seed: u64| {
+        let random = Random::for_table(4);
+        let players = Players::new(
+            Player::from(random),
+            Player::from(random),
+            Player::from(random),
+            Player::from(random),
+        );
+        Game::default(players, Order::default(), StdRng::seed_from_u64(seed))
+    };
+    for seed in 0..10 {
+        let Ok(mut engine) = Engine::new(game(seed)) else {
+            panic!("the stack should be dealt");
+        };
+        while let Some(decision) = engine.pending_decision().cloned() {
+            // Wrong answers are refused, the deal waiting for the same decision.
+            assert!(engine.apply(Action::Bid(Bid::Pass)).is_err());
+            if let Decision::PlayCard { choices, .. } = &decision {
+                if let Some(card) = (CardSet::ALL - choices.legal()).into_iter().next() {
+                    assert!(engine.apply(Action::PlayCard(card)).is_err());
+                }
+            }
+            if let Decision::ChooseColor { card_returned, .. } = &decision {
+                let returned = Trump::Color(card_returned.color());
+                assert!(engine.apply(Action::ChooseColor(Some(returned))).is_err());
+            }
+            assert_eq!(engine.pending_decision(), Some(&decision));
+            assert!(!engine.is_over());
+            let Some(action) = engine.ask() else {
+                panic!("random agents never interrupt");
+            };
+            assert_eq!(decision.check(&action), Ok(()));
+            let previous = engine.last_trick().copied();
+            assert_eq!(engine.apply(action), Ok(()));
+            // The winner of a trick leads the next one.
+            if let Some(trick) = engine
+                .last_trick()
+                .filter(|trick| Some(**trick) != previous)
+            {
+                assert_eq!(
+                    engine.pending_decision().map(Decision::position),
+                    Some(trick.winner())
+                );
+                if let Some(previous) = previous {
+                    assert_eq!(trick.leader(), previous.winner());
+                }
+            }
+        }
+        assert!(engine.is_over());
+        assert!(engine.apply(Action::Take(true)).is_err());
+
+        let points = engine.into_game().map(|game| game.points());
+        let Ok(blocking) = game(seed).distribute().and_then(Game::bidding) else {
+            panic!("the stack should be dealt");
+        };
+        let expected = match blocking.playing_game_or_redistribute() {
+            Ok(PlayOrNext::NextGame(next_game)) => next_game.points(),
+            Ok(PlayOrNext::PlayGame(playing)) => match playing.play() {
+                Ok(crate::playing::NextGameOrInterrupt::NextGame(next_game)) => next_game.points(),
+                _ => panic!("deal should be played"),
+            },
+            _ => panic!("bidding should end"),
+        };
+        assert_eq!(points, Some(expected));
+    }
+}
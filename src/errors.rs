@@ -12,6 +12,10 @@ pub enum BeloteErrorKind {
     InvalidValue(String),
     #[error("No taker or auctions not finished")]
     NoTaker,
+    #[error("Invalid record : {0}")]
+    InvalidRecord(String),
+    #[error("Invalid rules : {0}")]
+    InvalidRules(String),
 }
 
 pub trait ErrOnSome {
@@ -0,0 +1,176 @@
+use crate::announce::Announce;
+use crate::bidding::Bid;
+use crate::card::{Card, Trump};
+use crate::order::Order;
+use crate::playing::Outcome;
+use crate::points::Points;
+use crate::position::Position;
+use crate::rules::RuleSet;
+use crate::stack::Stack;
+use crate::team::Team;
+use crate::trick::Trick;
+use crate::variant::Variant;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Everything happening during a game, in the order it happens.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum GameEvent {
+    /// The stack is given once cut, just before being dealt.
+    DealStarted {
+        number: u64,
+        order: Order,
+        stack: Stack,
+        litige: u64,
+        #[serde(default)]
+        variant: Variant,
+        #[serde(default)]
+        rules: RuleSet,
+    },
+    CardReturned {
+        card: Card,
+    },
+    BidMade {
+        position: Position,
+        bid: Bid,
+    },
+    TrumpChosen {
+        taker: Position,
+        trump: Trump,
+    },
+    AnnounceDeclared {
+        position: Position,
+        announce: Announce,
+    },
+    /// The holder of the trump king and queen declares it while playing the first of them.
+    Belote {
+        position: Position,
+    },
+    /// ... then while playing the second one, the 20 points being won.
+    Rebelote {
+        position: Position,
+    },
+    CardPlayed {
+        position: Position,
+        card: Card,
+    },
+    TrickWon {
+        trick: Trick,
+        points: u64,
+    },
+    DealScored {
+        taker: Position,
+        attack_points: u64,
+        defense_points: u64,
+        belote_rebelote: Option<Team>,
+        /// Points of the announces, won by a single team.
+        #[serde(default)]
+        announces: Points,
+        outcome: Outcome,
+        points: Points,
+    },
+    LitigeCarried {
+        litige: u64,
+    },
+    MatchEnded {
+        points: Points,
+        winner: Option<Team>,
+    },
+}
+
+/// Subscriber to the events of a game.
+pub trait Observer: fmt::Debug {
+    fn notify(&mut self, event: &GameEvent);
+}
+
+/// Keeps every event, mostly useful for tests.
+impl Observer for Vec<GameEvent> {
+    fn notify(&mut self, event: &GameEvent) {
+        self.push(*event);
+    }
+}
+
+/// Shared observer, so that its state can still be read once given to a game.
+impl<O: Observer> Observer for Arc<Mutex<O>> {
+    fn notify(&mut self, event: &GameEvent) {
+        if let Ok(mut observer) = self.lock() {
+            observer.notify(event);
+        }
+    }
+}
+
+#[test]
+fn event_tests() {
+    use crate::agent::Random;
+    use crate::bidding::{Bidding, PlayOrNext};
+    use crate::game::Game;
+    use crate::player::Player;
+    use crate::players::Players;
+    use crate::playing::NextGameOrInterrupt;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let random = Random::new(1.0);
+    let players = Players::new(
+        Player::from(random),
+        Player::from(random),
+        Player::from(random),
+        Player::from(random),
+    );
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut game = Game::default(players, Order::default(), StdRng::seed_from_u64(0));
+    game.observe(Arc::clone(&events));
+    let Ok(PlayOrNext::PlayGame(playing)) = game
+        .distribute()
+        .and_then(Game::bidding)
+        .and_then(Game::<Bidding>::playing_game_or_redistribute)
+    else {
+        panic!("first player should take");
+    };
+    let Ok(NextGameOrInterrupt::NextGame(next_game)) = playing.play() else {
+        panic!("deal should be played");
+    };
+    let points = next_game.end(None);
+
+    let Ok(events) = events.lock() else {
+        panic!("cannot read events");
+    };
+    assert!(matches!(
+        events[0],
+        GameEvent::DealStarted { number: 0, .. }
+    ));
+    assert!(matches!(events[1], GameEvent::CardReturned { .. }));
+    assert!(matches!(
+        events[2],
+        GameEvent::BidMade {
+            position: Position::North,
+            bid: Bid::Take
+        }
+    ));
+    assert!(matches!(events[3], GameEvent::TrumpChosen { .. }));
+    let played = events
+        .iter()
+        .filter(|event| matches!(event, GameEvent::CardPlayed { .. }))
+        .count();
+    assert_eq!(played, 32);
+    let tricks = events
+        .iter()
+        .filter(|event| matches!(event, GameEvent::TrickWon { .. }))
+        .count();
+    assert_eq!(tricks, 8);
+    let rebelote = events
+        .iter()
+        .any(|event| matches!(event, GameEvent::Rebelote { .. }));
+    assert!(events.iter().any(|event| matches!(
+        event,
+        GameEvent::DealScored { belote_rebelote, .. } if belote_rebelote.is_some() == rebelote
+    )));
+    assert_eq!(
+        events.last(),
+        Some(&GameEvent::MatchEnded {
+            points,
+            winner: None
+        })
+    );
+}
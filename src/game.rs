@@ -1,13 +1,25 @@
+use crate::engine::{Action, Decision};
+use crate::event::{GameEvent, Observer};
 use crate::players::Players;
 use crate::points::Points;
+use crate::position::Position;
+use crate::rules::RuleSet;
 use crate::team::Team;
-
 use derive_more::{Constructor, Deref, DerefMut};
+use rand::rngs::StdRng;
+use strum::IntoEnumIterator;
 
+/// A game of belote, the phase it is in being given by `State`.
+///
+/// Players, cumulated points, the random generator and the rules are kept across phases, the phase itself is reachable through `Deref`.
+/// Every random draw of the game (shuffle, cut, agents) comes from this generator, so a seed gives back the same game.
 #[derive(Debug, Constructor, Deref, DerefMut)]
 pub struct Game<State> {
     players: Players,
     points: Points,
+    rng: StdRng,
+    rules: RuleSet,
+    observers: Vec<Box<dyn Observer>>,
     #[deref]
     #[deref_mut]
     state: State,
@@ -17,8 +29,48 @@ impl<State> Game<State> {
     pub fn into(self) -> State {
         self.state
     }
-    pub const fn is_full_random(&self) -> bool {
-        self.players.full_random()
+    /// Moves players, points, random generator and rules to the next phase.
+    pub fn map<Next>(self, next: impl FnOnce(State) -> Next) -> Game<Next> {
+        Game::new(
+            self.players,
+            self.points,
+            self.rng,
+            self.rules,
+            self.observers,
+            next(self.state),
+        )
+    }
+    /// Asks the agent of the seat which must take `decision`, giving it the view of the seat
+    /// and the random generator of the game. `None` means it interrupted the game.
+    pub fn decide(&mut self, decision: &Decision) -> Option<Action> {
+        let player = &mut self.players[decision.position()];
+        player.view_mut().set_hand(decision.hand());
+        let (agent, view) = player.agent_and_view();
+        decision.ask(agent, view, &mut self.rng)
+    }
+    /// Subscribes `observer` to the events of the game.
+    pub fn observe(&mut self, observer: impl Observer + 'static) {
+        self.observers.push(Box::new(observer));
+    }
+    /// Publishes `event` to every observer, and what the table sees of it to every seat.
+    pub fn notify(&mut self, event: GameEvent) {
+        for observer in &mut self.observers {
+            observer.notify(&event);
+        }
+        for position in Position::iter() {
+            let view = self.players[position].view_mut();
+            view.notify(&event);
+            view.set_points(self.points);
+        }
+    }
+    /// Ends the game, publishing the final points and the winner if any.
+    pub fn end(mut self, winner: Option<Team>) -> Points {
+        let points = self.points;
+        self.notify(GameEvent::MatchEnded { points, winner });
+        points
+    }
+    pub fn has_human(&self) -> bool {
+        self.players.has_human()
     }
     pub const fn points(&self) -> Points {
         self.points
@@ -26,7 +78,20 @@ impl<State> Game<State> {
     pub fn add_points(&mut self, team: Team, points: u64) {
         self.points[team] += points;
     }
-    pub const fn players(&self) -> Players {
-        self.players
+    pub const fn rules(&self) -> RuleSet {
+        self.rules
+    }
+    pub const fn set_rules(&mut self, rules: RuleSet) {
+        self.rules = rules;
+    }
+    pub const fn players(&self) -> &Players {
+        &self.players
+    }
+    pub const fn players_mut(&mut self) -> &mut Players {
+        &mut self.players
+    }
+    /// Borrows the phase and the random generator at once.
+    pub const fn state_and_rng(&mut self) -> (&mut State, &mut StdRng) {
+        (&mut self.state, &mut self.rng)
     }
 }
@@ -1,70 +1,111 @@
+use crate::announce::{Announce, SEQUENCE};
 use crate::card::{Card, Color, Value};
+use crate::card_set::{CardSet, Cards};
 use crate::constants::MAX_CARDS_BY_PLAYER;
-use crate::errors::{BeloteErrorKind, ErrOnSome};
-use derive_more::{Index, IntoIterator};
+use crate::errors::BeloteErrorKind;
 use std::fmt;
-use tinyvec::ArrayVec;
+use strum::IntoEnumIterator;
 
-#[derive(Default, Clone, Debug, Index, Copy, IntoIterator)]
-pub struct Hand(ArrayVec<[Option<Card>; MAX_CARDS_BY_PLAYER]>);
+/// Cards held by a player, at most 8, iterated by color then value.
+#[derive(Default, Clone, Debug, Copy, Eq, PartialEq, Hash)]
+pub struct Hand(CardSet);
 
 impl Hand {
     pub fn belote_rebelote(&self, trump_color: Color) -> bool {
-        let mut queen = false;
-        let mut king = false;
-        for card in self.into_iter().flatten() {
-            if card.color() == trump_color {
-                if card.value() == Value::Queen {
-                    queen = true;
-                } else if card.value() == Value::King {
-                    king = true;
+        [Value::Queen, Value::King]
+            .into_iter()
+            .all(|value| Card::new(trump_color, value).is_some_and(|card| self.contains(&card)))
+    }
+    /// Every sequence of at least 3 cards and every square this hand can declare.
+    pub fn announces(&self) -> Vec<Announce> {
+        let mut announces = Vec::new();
+        for color in Color::iter() {
+            let mut length = 0;
+            for (rank, value) in SEQUENCE.iter().enumerate() {
+                let held = Card::new(color, *value).is_some_and(|card| self.contains(&card));
+                if held {
+                    length += 1;
+                }
+                let last = rank + 1 == SEQUENCE.len();
+                if length >= 3 && (!held || last) {
+                    let high = if held { rank } else { rank - 1 };
+                    announces.push(Announce::Sequence {
+                        color,
+                        length,
+                        high: SEQUENCE[high],
+                    });
+                }
+                if !held {
+                    length = 0;
                 }
             }
         }
-        queen && king
+        for value in [
+            Value::Jack,
+            Value::_9,
+            Value::As,
+            Value::_10,
+            Value::King,
+            Value::Queen,
+        ] {
+            if Color::iter()
+                .all(|color| Card::new(color, value).is_some_and(|card| self.contains(&card)))
+            {
+                announces.push(Announce::Square(value));
+            }
+        }
+        announces
+    }
+    pub const fn contains(&self, card: &Card) -> bool {
+        self.0.contains(*card)
     }
     pub fn take(&mut self, card: Card) -> Result<(), BeloteErrorKind> {
-        self.0.try_push(Some(card)).err_on_some(|| {
-            Err(BeloteErrorKind::InvalidCase(
+        if self.len() >= MAX_CARDS_BY_PLAYER {
+            return Err(BeloteErrorKind::InvalidCase(
                 "Cannot append card to hand".to_string(),
-            ))
-        })
+            ));
+        }
+        if !self.0.insert(card) {
+            return Err(BeloteErrorKind::InvalidCase(format!(
+                "{card} is already in hand"
+            )));
+        }
+        Ok(())
     }
-    pub fn len(&self) -> usize {
+    pub const fn len(&self) -> usize {
         self.0.len()
     }
-    pub fn is_empty(&self) -> bool {
+    pub const fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
-    pub fn give(&mut self, card: &Card) -> Option<Card> {
+    pub const fn give(&mut self, card: &Card) -> Option<Card> {
+        if self.0.remove(*card) {
+            Some(*card)
+        } else {
+            None
+        }
+    }
+    /// Cards held, as a set.
+    pub const fn cards(&self) -> CardSet {
         self.0
-            .iter()
-            .position(|c| *c == Some(*card))
-            .and_then(|i| self.0.remove(i))
+    }
+}
+
+impl IntoIterator for Hand {
+    type Item = Card;
+    type IntoIter = Cards;
+
+    fn into_iter(self) -> Cards {
+        self.0.into_iter()
     }
 }
 
 impl fmt::Display for Hand {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f)?;
-        for card in self.0.into_iter().flatten() {
+        for card in *self {
             writeln!(f, "\t{card}")?;
         }
         Ok(())
     }
 }
-
-// impl IntoIterator for Hand {
-//     type Item = Card;
-//     type IntoIter = std::iter::Flatten<std::vec::IntoIter<Option<Card>>>;
-
-//     fn into_iter(self) -> Self::IntoIter {
-//         let cards = vec![
-//             self.card0, self.card1, self.card2, self.card3, self.card4, self.card5, self.card6,
-//             self.card7,
-//         ]
-//         .into_iter()
-//         .flatten();
-//         cards.into_iter()
-//     }
-// }
@@ -2,6 +2,7 @@ use crate::hand::Hand;
 use crate::position::Position;
 use std::ops::{Index, IndexMut};
 
+/// Hands of the 4 players, indexed by [`Position`].
 #[derive(Default, Debug, Clone, Copy)]
 pub struct Hands {
     north: Hand,
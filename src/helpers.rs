@@ -1,31 +1,78 @@
-use crate::bidding::PlayOrNext;
+use crate::agent::Random;
 use crate::errors::BeloteErrorKind;
 use crate::game::Game;
 use crate::order::Order;
 use crate::player::Player;
 use crate::players::Players;
-use crate::playing::NextGameOrInterrupt;
+use crate::rules::RuleSet;
+use crate::statistics::Statistics;
+use crate::variant::Variant;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::sync::{Arc, Mutex};
 
-pub fn test_game(games: u64) -> Result<(), BeloteErrorKind> {
+/// Plays `games` deals of `variant` with `rules` between random players seeded by `seed`, used as a stress test.
+pub fn test_game(
+    games: u64,
+    seed: u64,
+    variant: Variant,
+    rules: RuleSet,
+) -> Result<Statistics, BeloteErrorKind> {
+    let random = Random::for_table(4);
     let players = Players::new(
-        Player::new(true),
-        Player::new(true),
-        Player::new(true),
-        Player::new(true),
+        Player::from(random),
+        Player::from(random),
+        Player::from(random),
+        Player::from(random),
     );
-    let order = Order::random();
-    let mut game = Game::default(players, order);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let order = Order::random(&mut rng);
+    let mut game = Game::default(players, order, rng);
+    game.set_variant(variant);
+    game.set_rules(rules);
+    let statistics = Arc::new(Mutex::new(Statistics::default()));
+    game.observe(statistics.clone());
+    let collected = || {
+        statistics
+            .lock()
+            .map(|statistics| statistics.clone())
+            .unwrap_or_default()
+    };
     for _ in 0..games {
-        let distribution = game.distribute()?;
-        let bidding = distribution.bidding()?;
-        game = match bidding.playing_game_or_redistribute()? {
-            PlayOrNext::NextGame(next_game) => next_game,
-            PlayOrNext::PlayGame(in_game) => match in_game.play()? {
-                NextGameOrInterrupt::NextGame(next_game) => next_game,
-                NextGameOrInterrupt::Interrupted => return Ok(()),
-            },
-            PlayOrNext::Interrupted => return Ok(()),
+        let Some(next_game) = game.play_deal()? else {
+            return Ok(collected());
         };
+        game = next_game;
+    }
+    game.end(None);
+    Ok(collected())
+}
+
+#[test]
+fn seed_tests() {
+    use crate::rules::{Dealing, Litige, Rounding};
+    use crate::team::Team;
+    use strum::IntoEnumIterator;
+
+    for variant in Variant::iter() {
+        let rules = RuleSet::default();
+        assert_eq!(
+            test_game(20, 42, variant, rules),
+            test_game(20, 42, variant, rules)
+        );
+        assert!(test_game(200, 7, variant, rules).is_ok());
+
+        let mut house_rules = RuleSet::default();
+        house_rules.set_overtrump_on_partner(true);
+        house_rules.set_dealing(Dealing::TwoThree);
+        house_rules.set_litige(Litige::Split);
+        house_rules.set_belote_on_failed_contract(false);
+        house_rules.set_rounding(Rounding::Tens);
+        let Ok(statistics) = test_game(200, 7, variant, house_rules) else {
+            panic!("house rules should be playable");
+        };
+        assert!(statistics.points()[Team::NorthSouth] % 10 == 0);
+        assert_eq!(statistics.deals(), 200);
+        assert_eq!(statistics.scored() + statistics.redeals(), 200);
     }
-    Ok(())
 }
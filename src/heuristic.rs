@@ -0,0 +1,276 @@
+use crate::agent::Agent;
+use crate::bidding::Bid;
+use crate::card::{Card, Trump};
+use crate::card_set::CardSet;
+use crate::coinche::{Bids, Level};
+use crate::constants::MAX_PLAYERS;
+use crate::position::Position;
+use crate::strength::{self, Strength};
+use crate::tracker::Tracker;
+use crate::view::PlayerView;
+use rand::RngCore;
+use strum::IntoEnumIterator;
+
+/// Agent following the usual rules of thumb of the game.
+///
+/// It takes on a strong hand, pulls trumps as taker, cashes its masters, loads the tricks of its
+/// partner, answers its calls and keeps its master trump for the dix de der.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Heuristic;
+
+/// What a seat reads of the deal from its view, built anew for every card.
+#[derive(Debug, Default)]
+struct Reading {
+    tracker: Tracker,
+    taker: Option<Position>,
+    /// First discard of each player, showing the color it wants to be played.
+    calls: Vec<(Position, Card)>,
+}
+
+impl Reading {
+    /// Catches up with the cards played so far, noting the first discard of each player.
+    fn from_view(view: &PlayerView) -> Self {
+        let mut reading = Self {
+            tracker: Tracker::from_view(view),
+            taker: view.taker(),
+            calls: Vec::new(),
+        };
+        let Some(trump) = view.trump() else {
+            return reading;
+        };
+        for trick in view.played().chunks(MAX_PLAYERS) {
+            let Some((_, led)) = trick.first() else {
+                continue;
+            };
+            for (position, card) in &trick[1..] {
+                if card.color() != led.color()
+                    && !trump.is_trump(card.color())
+                    && !reading.calls.iter().any(|(caller, _)| caller == position)
+                {
+                    reading.calls.push((*position, *card));
+                }
+            }
+        }
+        reading
+    }
+
+    /// Is `card` the highest card of its color still to be played ?
+    fn is_master(&self, card: Card, hand: CardSet, trump: Trump) -> bool {
+        (CardSet::above(card, trump) - self.tracker.gone() - hand).is_empty()
+    }
+
+    /// Trumps which may still be held by the other players.
+    fn other_trumps(&self, hand: CardSet, trump: Trump) -> usize {
+        (CardSet::trumps(trump) - self.tracker.gone() - hand).len()
+    }
+
+    fn lead(&self, position: Position, hand: CardSet, choices: &[Card], trump: Trump) -> Card {
+        let is_trump = |card: &&Card| trump.is_trump(card.color());
+        if self.taker == Some(position)
+            && trump.color().is_some()
+            && self.other_trumps(hand, trump) > 0
+        {
+            let trumps = choices.iter().filter(is_trump);
+            if let Some(highest) = trumps.clone().max_by_key(|card| card.power(trump)) {
+                if self.is_master(*highest, hand, trump) {
+                    return *highest;
+                }
+                if let Some(lowest) = trumps.min_by_key(|card| card.power(trump)) {
+                    return *lowest;
+                }
+            }
+        }
+        if let Some(master) = choices
+            .iter()
+            .filter(|card| !is_trump(card) && self.is_master(**card, hand, trump))
+            .max_by_key(|card| card.points(trump))
+        {
+            return *master;
+        }
+        let called = self
+            .calls
+            .iter()
+            .filter(|(caller, _)| *caller == position.partner())
+            .find_map(|(_, call)| {
+                choices
+                    .iter()
+                    .filter(|card| card.color() == call.color())
+                    .min_by_key(|card| card.power(trump))
+            });
+        if let Some(called) = called {
+            return *called;
+        }
+        Self::discard(choices, trump, |card| self.is_master(card, hand, trump))
+    }
+
+    /// Cheapest card to lose, keeping trumps and masters.
+    fn discard(choices: &[Card], trump: Trump, is_master: impl Fn(Card) -> bool) -> Card {
+        choices
+            .iter()
+            .min_by_key(|card| {
+                (
+                    trump.is_trump(card.color()),
+                    is_master(**card),
+                    card.points(trump),
+                    card.power(trump),
+                )
+            })
+            .copied()
+            .unwrap_or(choices[0])
+    }
+
+    fn follow(&self, position: Position, hand: CardSet, choices: &[Card], trump: Trump) -> Card {
+        let Some((winner, winning)) = self.tracker.winning(trump) else {
+            return choices[0];
+        };
+        let last = self.tracker.trick().len() == MAX_PLAYERS - 1;
+        let is_master = |card: Card| self.is_master(card, hand, trump);
+        if winner.team() == position.team()
+            && (last || trump.is_trump(winning.color()) && is_master(winning))
+        {
+            return choices
+                .iter()
+                .filter(|card| !trump.is_trump(card.color()) && !is_master(**card))
+                .max_by_key(|card| card.points(trump))
+                .copied()
+                .unwrap_or_else(|| Self::discard(choices, trump, is_master));
+        }
+        let winners: Vec<Card> = choices
+            .iter()
+            .filter(|card| !winning.master(**card, trump))
+            .copied()
+            .collect();
+        if last {
+            if let Some(lowest) = winners.iter().min_by_key(|card| card.power(trump)) {
+                return *lowest;
+            }
+        } else if let Some(best) = winners
+            .iter()
+            .filter(|card| is_master(**card))
+            .min_by_key(|card| card.power(trump))
+            .or_else(|| winners.iter().max_by_key(|card| card.power(trump)))
+        {
+            return *best;
+        }
+        Self::discard(choices, trump, is_master)
+    }
+}
+
+impl Agent for Heuristic {
+    fn take(
+        &mut self,
+        view: &PlayerView,
+        card_returned: Card,
+        _: &mut dyn RngCore,
+    ) -> Option<bool> {
+        let trump = Trump::Color(card_returned.color());
+        Some(strength::evaluate(&view.hand(), Some(card_returned), trump).take())
+    }
+
+    fn choose_color(
+        &mut self,
+        view: &PlayerView,
+        card_returned: Card,
+        _: &mut dyn RngCore,
+    ) -> Option<Option<Trump>> {
+        Some(
+            strength::advise(&view.hand(), Some(card_returned))
+                .into_iter()
+                .find(|strength| strength.trump() != Trump::Color(card_returned.color()))
+                .filter(Strength::take)
+                .map(|strength| strength.trump()),
+        )
+    }
+
+    /// Announces the highest level its hand is worth, without ever outbidding its partner.
+    fn bid(&mut self, view: &PlayerView, bids: &Bids, _: &mut dyn RngCore) -> Option<Bid> {
+        let position = view.position();
+        if bids
+            .best()
+            .is_some_and(|(bidder, _, _)| bidder.team() == position.team())
+        {
+            return Some(Bid::Pass);
+        }
+        let Some(best) = strength::advise(&view.hand(), None).into_iter().next() else {
+            return Some(Bid::Pass);
+        };
+        let trump = best.trump();
+        let worth = best.expected() / 10 * 10;
+        let legal = bids.legal(position);
+        Some(
+            Level::iter()
+                .rev()
+                .filter(|level| level.value() <= worth.min(160))
+                .map(|level| Bid::Announce { level, trump })
+                .find(|bid| legal.contains(bid))
+                .unwrap_or(Bid::Pass),
+        )
+    }
+
+    fn choose_card(
+        &mut self,
+        view: &PlayerView,
+        choices: &[Card],
+        _: &mut dyn RngCore,
+    ) -> Option<Card> {
+        let reading = Reading::from_view(view);
+        let Some(trump) = reading.tracker.trump() else {
+            return choices.first().copied();
+        };
+        if choices.is_empty() {
+            return None;
+        }
+        let hand = view.hand().cards();
+        if let [first, second] = choices {
+            let master_trump =
+                |card: Card| trump.is_trump(card.color()) && reading.is_master(card, hand, trump);
+            if hand.len() == 2 && master_trump(*first) != master_trump(*second) {
+                return Some(if master_trump(*first) {
+                    *second
+                } else {
+                    *first
+                });
+            }
+        }
+        Some(if reading.tracker.trick().is_empty() {
+            reading.lead(view.position(), hand, choices, trump)
+        } else {
+            reading.follow(view.position(), hand, choices, trump)
+        })
+    }
+}
+
+#[test]
+fn heuristic_tests() {
+    use crate::agent::Random;
+    use crate::game::Game;
+    use crate::order::Order;
+    use crate::player::Player;
+    use crate::players::Players;
+    use crate::team::Team;
+    use crate::variant::Variant;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    for variant in Variant::iter() {
+        let players = Players::new(
+            Player::from(Heuristic),
+            Player::from(Heuristic),
+            Player::from(Random::for_table(2)),
+            Player::from(Random::for_table(2)),
+        );
+        let mut game = Game::default(players, Order::default(), StdRng::seed_from_u64(0));
+        game.set_variant(variant);
+        for _ in 0..200 {
+            let Ok(Some(next_game)) = game.play_deal() else {
+                panic!("heuristic should only play legal cards");
+            };
+            game = next_game;
+        }
+        let points = game.points();
+        assert!(
+            points[Team::NorthSouth] > points[Team::WestEast],
+            "{variant}"
+        );
+    }
+}
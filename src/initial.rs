@@ -1,78 +1,153 @@
 use crate::distribution::Distribution;
+use crate::engine::{Action, Engine};
 use crate::errors::BeloteErrorKind;
+use crate::event::GameEvent;
 use crate::game::Game;
 use crate::hands::Hands;
 use crate::order::Order;
 use crate::players::Players;
 use crate::points::Points;
+use crate::rules::RuleSet;
 use crate::stack::Stack;
+use crate::variant::Variant;
 use derive_new::new;
+use rand::rngs::StdRng;
 use tracing::info;
 
-#[derive(new)]
+/// Phase before a deal : the stack is gathered and the order of players is known.
+#[derive(new, Debug)]
 pub struct Initial {
     order: Order,
     #[new(default)]
     number: u64,
-    #[new(value = "Stack::random()")]
     stack: Stack,
     #[new(default)]
     litige: u64,
+    #[new(default)]
+    variant: Variant,
 }
 
 impl Initial {
+    /// Phase of a deal already started, to play it again.
+    pub const fn restore(
+        order: Order,
+        number: u64,
+        stack: Stack,
+        litige: u64,
+        variant: Variant,
+    ) -> Self {
+        Self {
+            order,
+            number,
+            stack,
+            litige,
+            variant,
+        }
+    }
     #[must_use]
-    pub fn next(mut self) -> Self {
+    pub const fn next(mut self) -> Self {
         self.order.rotate();
         self.number += 1;
-        self.stack.cut();
         self
     }
 
-    pub fn add_litige(&mut self, litige: u64) {
+    pub const fn add_litige(&mut self, litige: u64) {
         self.litige += litige;
     }
-    pub fn reset_litige(&mut self) -> u64 {
+    pub const fn reset_litige(&mut self) -> u64 {
         let old_litige = self.litige;
         self.litige = 0;
         old_litige
     }
+    pub const fn litige(&self) -> u64 {
+        self.litige
+    }
     pub const fn order(&self) -> Order {
         self.order
     }
     pub const fn number(&self) -> u64 {
         self.number
     }
+    pub const fn variant(&self) -> Variant {
+        self.variant
+    }
+    pub const fn set_variant(&mut self, variant: Variant) {
+        self.variant = variant;
+    }
     pub const fn stack(&self) -> &Stack {
         &self.stack
     }
-    pub fn stack_mut(&mut self) -> &mut Stack {
+    pub const fn stack_mut(&mut self) -> &mut Stack {
         &mut self.stack
     }
 }
 
 impl Game<Initial> {
-    pub fn default(players: Players, order: Order) -> Self {
-        Self::new(players, Points::default(), Initial::new(order))
+    /// Starts a new game with a stack shuffled by `rng` and no points.
+    pub fn default(players: Players, order: Order, mut rng: StdRng) -> Self {
+        let stack = Stack::random(&mut rng);
+        Self::new(
+            players,
+            Points::default(),
+            rng,
+            RuleSet::default(),
+            Vec::new(),
+            Initial::new(order, stack),
+        )
     }
 
+    /// Plays a whole deal, `None` meaning an agent interrupted it.
+    pub fn play_deal(self) -> Result<Option<Self>, BeloteErrorKind> {
+        self.play_deal_with(Engine::ask)
+    }
+
+    /// Plays a whole deal, every decision being answered by `decide`, `None` meaning it
+    /// interrupted the deal.
+    pub fn play_deal_with(
+        self,
+        mut decide: impl FnMut(&mut Engine) -> Option<Action>,
+    ) -> Result<Option<Self>, BeloteErrorKind> {
+        let mut engine = Engine::new(self)?;
+        while !engine.is_over() {
+            let Some(action) = decide(&mut engine) else {
+                info!("Interrupted.");
+                return Ok(None);
+            };
+            engine.apply(action)?;
+        }
+        Ok(engine.into_game())
+    }
+
+    /// Cuts the stack, then deals it.
     pub fn distribute(mut self) -> Result<Game<Distribution>, BeloteErrorKind> {
+        let (initial, rng) = self.state_and_rng();
+        initial.stack.cut(rng);
+        self.deal()
+    }
+
+    /// Deals 5 cards to every player in two rounds, 3 then 2 by default, without cutting the stack.
+    pub fn deal(mut self) -> Result<Game<Distribution>, BeloteErrorKind> {
+        self.notify(GameEvent::DealStarted {
+            number: self.number(),
+            order: self.order(),
+            stack: self.stack,
+            litige: self.litige,
+            variant: self.variant,
+            rules: self.rules(),
+        });
+        let [first, second] = self.rules().dealing().rounds();
         let mut hands = Hands::default();
         for position in self.order() {
-            for _ in 0..3 {
+            for _ in 0..first {
                 hands[position].take(self.stack.give_card()?)?;
             }
         }
         for position in self.order() {
-            for _ in 0..2 {
+            for _ in 0..second {
                 hands[position].take(self.stack.give_card()?)?;
             }
             info!("{position} : {}", hands[position]);
         }
-        Ok(Game::new(
-            self.players(),
-            self.points(),
-            Distribution::new(hands, self.into()),
-        ))
+        Ok(self.map(|initial| Distribution::new(hands, initial)))
     }
 }
@@ -0,0 +1,277 @@
+use crate::agent::Agent;
+use crate::announce::Announce;
+use crate::bidding::Bid;
+use crate::card::{Card, Trump};
+use crate::card_set::CardSet;
+use crate::coinche::Bids;
+use crate::heuristic::Heuristic;
+use crate::position::Position;
+use crate::solver::Layout;
+use crate::team::Team;
+use crate::tracker::Tracker;
+use crate::view::PlayerView;
+use rand::seq::{IteratorRandom, SliceRandom};
+use rand::RngCore;
+
+/// Move of the search tree with its statistics, the reward being the share of the points won by
+/// the team having played it.
+#[derive(Debug, Clone, Default)]
+struct Node {
+    action: Option<(Position, Card)>,
+    children: Vec<usize>,
+    visits: u32,
+    /// Iterations where this move was legal.
+    available: u32,
+    reward: f64,
+}
+
+impl Node {
+    fn upper_bound(&self, exploration: f64) -> f64 {
+        let visits = f64::from(self.visits);
+        exploration.mul_add(
+            (f64::from(self.available).ln() / visits).sqrt(),
+            self.reward / visits,
+        )
+    }
+}
+
+/// Agent playing by Information Set Monte Carlo Tree Search.
+///
+/// Each iteration deals the unseen cards again from what the [`Tracker`] saw, then walks down a
+/// single tree shared by all deals, among the moves legal in the one drawn. The deal is finished
+/// by random cards and every move of the walk is rewarded by the points its team won.
+/// Bidding is left to the [`Heuristic`].
+#[derive(Debug, Clone)]
+pub struct Ismcts {
+    iterations: usize,
+    exploration: f64,
+    heuristic: Heuristic,
+}
+
+impl Default for Ismcts {
+    fn default() -> Self {
+        Self::new(1000, 0.7)
+    }
+}
+
+impl Ismcts {
+    /// Agent running `iterations` walks for each card, `exploration` weighting the moves seldom
+    /// tried against the best ones.
+    pub const fn new(iterations: usize, exploration: f64) -> Self {
+        Self {
+            iterations,
+            exploration,
+            heuristic: Heuristic,
+        }
+    }
+
+    pub const fn iterations(&self) -> usize {
+        self.iterations
+    }
+    pub const fn exploration(&self) -> f64 {
+        self.exploration
+    }
+
+    /// Most visited card of `position` after the search.
+    fn search(
+        &self,
+        tracker: &Tracker,
+        position: Position,
+        hand: CardSet,
+        trump: Trump,
+        rng: &mut dyn RngCore,
+    ) -> Option<Card> {
+        let mut tree = vec![Node::default()];
+        for _ in 0..self.iterations.max(1) {
+            let hands = tracker.sample(position, hand, rng)?;
+            let mut layout = Layout::new(
+                hands,
+                tracker.trick(),
+                tracker.leader(position),
+                trump,
+                tracker.rules(),
+            )
+            .ok()?;
+            let mut won = [0; 2];
+            let mut path = vec![0];
+            let mut node = 0;
+
+            // Selection, until a move never tried in this deal is expanded.
+            while !layout.is_over() {
+                let mover = layout.next();
+                let legal = layout.choices().ok()?;
+                let mut untried = Vec::new();
+                let mut tried = Vec::new();
+                for card in legal {
+                    let child = tree[node]
+                        .children
+                        .iter()
+                        .copied()
+                        .find(|child| tree[*child].action == Some((mover, card)));
+                    match child {
+                        Some(child) => tried.push(child),
+                        None => untried.push(card),
+                    }
+                }
+                for child in &tried {
+                    tree[*child].available += 1;
+                }
+                let (child, card) = if let Some(card) = untried.choose(rng) {
+                    tree.push(Node {
+                        action: Some((mover, *card)),
+                        available: 1,
+                        ..Node::default()
+                    });
+                    let child = tree.len() - 1;
+                    tree[node].children.push(child);
+                    (child, *card)
+                } else {
+                    let child = tried.into_iter().max_by(|a, b| {
+                        tree[*a]
+                            .upper_bound(self.exploration)
+                            .total_cmp(&tree[*b].upper_bound(self.exploration))
+                    })?;
+                    (child, tree[child].action?.1)
+                };
+                layout = Self::play(&layout, card, &mut won)?;
+                path.push(child);
+                node = child;
+                if tree[child].visits == 0 {
+                    break;
+                }
+            }
+
+            // Simulation.
+            while !layout.is_over() {
+                let choices = layout.choices().ok()?;
+                let card = choices.into_iter().choose(rng)?;
+                layout = Self::play(&layout, card, &mut won)?;
+            }
+
+            // Backpropagation.
+            let total = won[0] + won[1];
+            for index in path {
+                let node = &mut tree[index];
+                node.visits += 1;
+                if let Some((mover, _)) = node.action {
+                    node.reward += share(won[team(mover)], total);
+                }
+            }
+        }
+        tree[0]
+            .children
+            .iter()
+            .max_by_key(|child| tree[**child].visits)
+            .and_then(|child| tree[*child].action)
+            .map(|(_, card)| card)
+    }
+
+    fn play(layout: &Layout, card: Card, won: &mut [u64; 2]) -> Option<Layout> {
+        let (layout, trick) = layout.play(card).ok()?;
+        if let Some((winner, points)) = trick {
+            won[team(winner)] += points;
+        }
+        Some(layout)
+    }
+}
+
+const fn team(position: Position) -> usize {
+    match position.team() {
+        Team::NorthSouth => 0,
+        Team::WestEast => 1,
+    }
+}
+
+fn share(points: u64, total: u64) -> f64 {
+    let points = u32::try_from(points).unwrap_or_default();
+    let total = u32::try_from(total).unwrap_or_default().max(1);
+    f64::from(points) / f64::from(total)
+}
+
+impl Agent for Ismcts {
+    fn take(
+        &mut self,
+        view: &PlayerView,
+        card_returned: Card,
+        rng: &mut dyn RngCore,
+    ) -> Option<bool> {
+        self.heuristic.take(view, card_returned, rng)
+    }
+
+    fn choose_color(
+        &mut self,
+        view: &PlayerView,
+        card_returned: Card,
+        rng: &mut dyn RngCore,
+    ) -> Option<Option<Trump>> {
+        self.heuristic.choose_color(view, card_returned, rng)
+    }
+
+    fn bid(&mut self, view: &PlayerView, bids: &Bids, rng: &mut dyn RngCore) -> Option<Bid> {
+        self.heuristic.bid(view, bids, rng)
+    }
+
+    fn declare(
+        &mut self,
+        view: &PlayerView,
+        announces: &[Announce],
+        rng: &mut dyn RngCore,
+    ) -> Option<Vec<Announce>> {
+        self.heuristic.declare(view, announces, rng)
+    }
+
+    fn declare_belote(
+        &mut self,
+        view: &PlayerView,
+        card: Card,
+        rng: &mut dyn RngCore,
+    ) -> Option<bool> {
+        self.heuristic.declare_belote(view, card, rng)
+    }
+
+    fn choose_card(
+        &mut self,
+        view: &PlayerView,
+        choices: &[Card],
+        rng: &mut dyn RngCore,
+    ) -> Option<Card> {
+        let tracker = Tracker::from_view(view);
+        let position = view.position();
+        let Some(trump) = tracker.trump() else {
+            return choices.first().copied();
+        };
+        if choices.len() < 2 {
+            return choices.first().copied();
+        }
+        self.search(&tracker, position, view.hand().cards(), trump, rng)
+            .filter(|card| choices.contains(card))
+            .or_else(|| choices.first().copied())
+    }
+}
+
+#[test]
+fn ismcts_tests() {
+    use crate::agent::Random;
+    use crate::game::Game;
+    use crate::order::Order;
+    use crate::player::Player;
+    use crate::players::Players;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let players = Players::new(
+        Player::from(Ismcts::new(50, 0.7)),
+        Player::from(Ismcts::new(50, 0.7)),
+        Player::from(Random::for_table(2)),
+        Player::from(Random::for_table(2)),
+    );
+    let mut game = Game::default(players, Order::default(), StdRng::seed_from_u64(0));
+    for _ in 0..40 {
+        let Ok(Some(next_game)) = game.play_deal() else {
+            panic!("ismcts should only play legal cards");
+        };
+        game = next_game;
+    }
+    let points = game.points();
+    assert!(points[Team::NorthSouth] > points[Team::WestEast]);
+}
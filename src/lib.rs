@@ -0,0 +1,66 @@
+//! Belote engine: card model, deal, bidding, trick play and scoring.
+//!
+//! A game is a typestate machine, each phase being a `Game<State>` :
+//!
+//! ```text
+//! Game<Initial> --distribute--> Game<Distribution> --bidding--> Game<Bidding>
+//!       ^                                                            |
+//!       |                                      playing_game_or_redistribute
+//!       |                                                            |
+//!       +----------------- play ----- Game<Playing> <----------------+
+//! ```
+//!
+//! Coinche replaces `Game<Bidding>` by an auction, `Game<Auction>`, once all cards are dealt.
+//!
+//! Each phase advances one decision at a time, with `pending_decision` and `apply`. An
+//! [`engine::Engine`] chains them over a whole deal, so that a frontend can prompt its humans
+//! itself instead of being called from inside the phases.
+//!
+//! Decisions are not taken by the engine itself, each seat delegates them to an
+//! [`agent::Agent`], the frontend providing its own agents for humans.
+//! What happens during the game is published as [`event::GameEvent`] to the
+//! [`event::Observer`] subscribed to it, a [`record::Recorder`] keeping them
+//! so that a game can be saved and replayed.
+
+pub mod agent;
+pub mod announce;
+pub mod arena;
+pub mod belote;
+pub mod bidding;
+pub mod card;
+pub mod card_set;
+pub mod coinche;
+pub mod constants;
+pub mod contract;
+pub mod distribution;
+pub mod engine;
+pub mod errors;
+pub mod event;
+pub mod game;
+pub mod hand;
+pub mod hands;
+pub mod helpers;
+pub mod heuristic;
+pub mod initial;
+pub mod ismcts;
+pub mod match_play;
+pub mod order;
+pub mod pimc;
+pub mod player;
+pub mod players;
+pub mod playing;
+pub mod points;
+pub mod position;
+pub mod record;
+pub mod rules;
+pub mod simulator;
+pub mod solver;
+pub mod stack;
+pub mod statistics;
+pub mod strength;
+pub mod team;
+pub mod tracker;
+pub mod trick;
+pub mod turn;
+pub mod variant;
+pub mod view;
@@ -1,160 +1,663 @@
-use crate::bidding::PlayOrNext;
-use crate::game::Game;
-use crate::order::Order;
-use crate::player::Player;
-use crate::players::Players;
-use crate::playing::NextGameOrInterrupt;
-use crate::team::Team;
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 use color_eyre::eyre::Result;
-use inquire::Confirm;
+use inquire::{Confirm, MultiSelect, Select};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use rbelote::agent::{Agent, Random};
+use rbelote::announce::Announce;
+use rbelote::arena::{Arena, Entrant};
+use rbelote::bidding::Bid;
+use rbelote::card::{Card, Trump};
+use rbelote::coinche::Bids;
+use rbelote::contract::Contract;
+use rbelote::engine::{Action, Decision, Engine};
+use rbelote::errors::BeloteErrorKind;
+use rbelote::game::Game;
+use rbelote::hand::Hand;
+use rbelote::hands::Hands;
+use rbelote::heuristic::Heuristic;
+use rbelote::ismcts::Ismcts;
+use rbelote::match_play::{Goal, Match, MatchState, Summary};
+use rbelote::order::Order;
+use rbelote::pimc::Pimc;
+use rbelote::player::{Choices, Player};
+use rbelote::players::Players;
+use rbelote::position::Position;
+use rbelote::record::{GameRecord, Recorder};
+use rbelote::rules::{Dealing, Litige, Rounding, RuleSet};
+use rbelote::simulator::Simulator;
+use rbelote::solver::{self, Layout};
+use rbelote::strength;
+use rbelote::team::Team;
+use rbelote::trick::Trick;
+use rbelote::variant::Variant;
+use rbelote::view::PlayerView;
 use std::error;
+use std::fs;
 use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::thread;
+use std::time::Duration;
 use strum::IntoEnumIterator;
 use tracing::{error, info};
 
-pub mod belote;
-pub mod bidding;
-pub mod card;
-pub mod constants;
-pub mod contract;
-pub mod distribution;
-pub mod errors;
-pub mod game;
-pub mod hand;
-pub mod hands;
-pub mod helpers;
-pub mod initial;
-pub mod order;
-pub mod player;
-pub mod players;
-pub mod playing;
-pub mod points;
-pub mod position;
-pub mod stack;
-pub mod team;
-pub mod turn;
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Replays a recorded game, checking every move and the final points
+    Replay {
+        /// Recorded game
+        file: PathBuf,
+    },
+    /// Solves a deal with every hand known, reporting the points of each team and the optimal
+    /// cards under perfect play
+    Solve {
+        /// Cards held by North, as "♥J ♥9 ♠11"
+        #[arg(long = "north")]
+        north: String,
+        /// Cards held by East
+        #[arg(long = "east")]
+        east: String,
+        /// Cards held by South
+        #[arg(long = "south")]
+        south: String,
+        /// Cards held by West
+        #[arg(long = "west")]
+        west: String,
+        /// Trump : a color, SA or TA
+        #[arg(long = "trump")]
+        trump: Trump,
+        /// Leader of the current trick
+        #[arg(long = "leader", default_value = "north")]
+        leader: Position,
+        /// Cards already played in the current trick, from its leader
+        #[arg(long = "trick", default_value = "")]
+        trick: String,
+    },
+    /// Pits agents against each other, every pair playing the same deals, then ranks them
+    Arena {
+        /// Agents taking part, as "heuristic,pimc,ismcts"
+        #[arg(long = "agents", value_enum, value_delimiter = ',', required = true)]
+        agents: Vec<Bot>,
+        /// Deals played by every pair of agents
+        #[arg(long = "deals", default_value_t = 100)]
+        deals: u64,
+        /// Plays every deal twice, the teams swapping their seats
+        #[arg(long = "duplicate", default_value_t = false)]
+        duplicate: bool,
+    },
+}
+
+/// Agent sitting at a seat.
+#[derive(ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+enum Seat {
+    Random,
+    Human,
+    Heuristic,
+    Pimc,
+    Ismcts,
+}
+
+/// Agent playing without a human, the only ones an arena can rank.
+#[derive(ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+enum Bot {
+    Random,
+    Heuristic,
+    Pimc,
+    Ismcts,
+}
+
+impl From<Bot> for Seat {
+    fn from(bot: Bot) -> Self {
+        match bot {
+            Bot::Random => Self::Random,
+            Bot::Heuristic => Self::Heuristic,
+            Bot::Pimc => Self::Pimc,
+            Bot::Ismcts => Self::Ismcts,
+        }
+    }
+}
 
 #[derive(Parser, Debug)]
 #[clap(author, about, version)]
 struct Opts {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Number of games to play
     #[arg(long = "games", default_value_t = 1)]
     games: u64,
 
-    /// Is North human ?
-    #[arg(long = "north", default_value_t = false)]
-    human_north: bool,
+    /// Play until a team reaches this score (501, 1000, 2000...) instead of a number of games
+    #[arg(long = "target")]
+    target: Option<u64>,
+
+    /// Who plays North, `--north` alone meaning human
+    #[arg(long = "north", value_enum, default_value_t = Seat::Random, num_args = 0..=1, default_missing_value = "human")]
+    north: Seat,
+
+    /// Who plays East, `--east` alone meaning human
+    #[arg(long = "east", value_enum, default_value_t = Seat::Random, num_args = 0..=1, default_missing_value = "human")]
+    east: Seat,
+
+    /// Who plays South, `--south` alone meaning human
+    #[arg(long = "south", value_enum, default_value_t = Seat::Random, num_args = 0..=1, default_missing_value = "human")]
+    south: Seat,
+
+    /// Who plays West, `--west` alone meaning human
+    #[arg(long = "west", value_enum, default_value_t = Seat::Random, num_args = 0..=1, default_missing_value = "human")]
+    west: Seat,
+
+    /// Deals solved by a pimc seat for each card
+    #[arg(long = "pimc-samples", default_value_t = 20)]
+    pimc_samples: usize,
 
-    /// Is East human ?
-    #[arg(long = "east", default_value_t = false)]
-    human_east: bool,
+    /// Time a pimc seat may spend on each card, in milliseconds, ignored with `--seed`, in the
+    /// arena and in test mode so that their games play again the same
+    #[arg(long = "pimc-budget-ms")]
+    pimc_budget_ms: Option<u64>,
 
-    /// Is South human ?
-    #[arg(long = "south", default_value_t = false)]
-    human_south: bool,
+    /// Walks of the search tree of an ismcts seat for each card
+    #[arg(long = "ismcts-iterations", default_value_t = 1000)]
+    ismcts_iterations: usize,
 
-    /// Is West human ?
-    #[arg(long = "west", default_value_t = false)]
-    human_west: bool,
+    /// Exploration constant of an ismcts seat
+    #[arg(long = "ismcts-exploration", default_value_t = 0.7)]
+    ismcts_exploration: f64,
+
+    /// Game played : belote or coinche
+    #[arg(long = "variant", default_value_t = Variant::Belote)]
+    variant: Variant,
+
+    /// House rules, as a JSON file, the options below overriding it
+    #[arg(long = "rules")]
+    rules: Option<PathBuf>,
+
+    /// Must a player overtrump even when its partner is master ?
+    #[arg(long = "overtrump-on-partner")]
+    overtrump_on_partner: Option<bool>,
+
+    /// Dealing of the first 5 cards : 3-2 or 2-3
+    #[arg(long = "dealing")]
+    dealing: Option<Dealing>,
+
+    /// Litige handling : carried, split or dedans
+    #[arg(long = "litige")]
+    litige: Option<Litige>,
+
+    /// Value of a capot
+    #[arg(long = "capot")]
+    capot: Option<u64>,
+
+    /// Does belote still count when its team is dedans ?
+    #[arg(long = "belote-on-failed-contract")]
+    belote_on_failed_contract: Option<bool>,
+
+    /// Score rounding : exact or tens
+    #[arg(long = "rounding")]
+    rounding: Option<Rounding>,
 
     /// Random order ?
     #[arg(long = "random-order", default_value_t = false)]
     random_order: bool,
 
-    /// Test mode
+    /// Test mode : plays `--games` single deal games between random players
     #[arg(short = 't', long = "test", default_value_t = false)]
     test: bool,
 
+    /// Plays again alone the test game with this seed, as logged when it failed
+    #[arg(long = "rerun", requires = "test")]
+    rerun: Option<u64>,
+
     /// Tracing ?
     #[arg(long = "trace", default_value_t = false)]
     tracing: bool,
 
-    /// Concurrency in test mode, default is number of cpu on this machine
+    /// Seed of the game, a random one is chosen and logged if not given
+    #[arg(long = "seed")]
+    seed: Option<u64>,
+
+    /// Record the game into this file
+    #[arg(long = "record")]
+    record: Option<PathBuf>,
+
+    /// Write the statistics of the test mode or the standings of the arena into this JSON file
+    #[arg(long = "report")]
+    report: Option<PathBuf>,
+
+    /// Threads sharing the games of the test mode, default is number of cpu on this machine
     #[arg(short = 'c', long = "concurrency", default_value_t = thread::available_parallelism().unwrap())]
     concurrency: NonZeroUsize,
 }
 
+/// Seat of a human : its decisions are prompted by [`decide`] while it drives the deal, the
+/// agent itself never answering.
+#[derive(Debug)]
+struct Terminal;
+
+impl Agent for Terminal {
+    fn take(&mut self, _: &PlayerView, _: Card, _: &mut dyn RngCore) -> Option<bool> {
+        None
+    }
+
+    fn choose_color(
+        &mut self,
+        _: &PlayerView,
+        _: Card,
+        _: &mut dyn RngCore,
+    ) -> Option<Option<Trump>> {
+        None
+    }
+
+    fn choose_card(&mut self, _: &PlayerView, _: &[Card], _: &mut dyn RngCore) -> Option<Card> {
+        None
+    }
+
+    fn is_human(&self) -> bool {
+        true
+    }
+}
+
+/// Answers the decision the deal waits for : prompted for a human seat, asked to the agent of
+/// the seat otherwise. `None` interrupts the deal.
+fn decide(engine: &mut Engine) -> Option<Action> {
+    let decision = engine.pending_decision()?;
+    if engine.is_human(decision.position()) {
+        prompt(decision, engine.last_trick())
+    } else {
+        engine.ask()
+    }
+}
+
+fn prompt(decision: &Decision, last_trick: Option<&Trick>) -> Option<Action> {
+    match decision {
+        Decision::Take {
+            hand,
+            card_returned,
+            ..
+        } => prompt_take(*hand, *card_returned).map(Action::Take),
+        Decision::ChooseColor {
+            hand,
+            card_returned,
+            trumps,
+            ..
+        } => prompt_color(*hand, *card_returned, trumps),
+        Decision::Bid {
+            position,
+            hand,
+            bids,
+        } => prompt_bid(*position, *hand, bids).map(Action::Bid),
+        Decision::Declare { announces, .. } => prompt_announces(announces).map(Action::Declare),
+        Decision::DeclareBelote { card, .. } => prompt_belote(*card).map(Action::DeclareBelote),
+        Decision::PlayCard { hand, choices, .. } => {
+            prompt_card(*hand, choices, last_trick).map(Action::PlayCard)
+        }
+    }
+}
+
+fn prompt_take(hand: Hand, card_returned: Card) -> Option<bool> {
+    let trump = Trump::Color(card_returned.color());
+    let advice = format!(
+        "Advisor : {}",
+        strength::evaluate(&hand, Some(card_returned), trump)
+    );
+    loop {
+        let answer = Confirm::new(&format!(
+            "Do you take {card_returned} with {} ? (ESC to cancel)",
+            hand.cards()
+        ))
+        .with_default(false)
+        .with_help_message(&advice)
+        .prompt_skippable();
+        match answer {
+            Ok(answer) => return answer,
+            Err(_) => {
+                info!("Error with questionnaire, try again.");
+            }
+        }
+    }
+}
+
+fn prompt_color(hand: Hand, card_returned: Card, trumps: &[Trump]) -> Option<Action> {
+    let advice = strength::advise(&hand, Some(card_returned))
+        .into_iter()
+        .find(|strength| trumps.contains(&strength.trump()))
+        .map(|strength| format!("Advisor : {strength}"))
+        .unwrap_or_default();
+    loop {
+        let contracts: Vec<Contract> = Contract::iter()
+            .filter(|contract| contract.trump().is_none_or(|trump| trumps.contains(&trump)))
+            .collect();
+        let answer = Select::new(
+            &format!(
+                "Which color do you choose with {} ? (ESC to cancel)",
+                hand.cards()
+            ),
+            contracts,
+        )
+        .with_help_message(&advice)
+        .prompt_skippable();
+        match answer {
+            Ok(Some(contract)) => return Some(Action::ChooseColor(contract.trump())),
+            Ok(None) => return None,
+            Err(_) => {
+                info!("Error with questionnaire, try again.");
+            }
+        }
+    }
+}
+
+fn prompt_bid(position: Position, hand: Hand, bids: &Bids) -> Option<Bid> {
+    let legal = bids.legal(position);
+    let page_size = legal.len().min(14);
+    let advice = strength::advise(&hand, None)
+        .first()
+        .map(|strength| format!("Advisor : {strength}"))
+        .unwrap_or_default();
+    loop {
+        let answer = Select::new(
+            &format!("What do you bid with {} ? (ESC to cancel)", hand.cards()),
+            legal.clone(),
+        )
+        .with_page_size(page_size)
+        .with_help_message(&advice)
+        .prompt_skippable();
+        match answer {
+            Ok(answer) => return answer,
+            Err(_) => {
+                info!("Error with questionnaire, try again.");
+            }
+        }
+    }
+}
+
+fn prompt_announces(announces: &[Announce]) -> Option<Vec<Announce>> {
+    loop {
+        let answer = MultiSelect::new(
+            "Which announces do you declare ? (ESC to cancel)",
+            announces.to_vec(),
+        )
+        .with_all_selected_by_default()
+        .prompt_skippable();
+        match answer {
+            Ok(answer) => return answer,
+            Err(_) => {
+                info!("Error with questionnaire, try again.");
+            }
+        }
+    }
+}
+
+fn prompt_belote(card: Card) -> Option<bool> {
+    loop {
+        let answer = Confirm::new(&format!(
+            "Do you announce belote with {card} ? (ESC to cancel)"
+        ))
+        .with_default(true)
+        .prompt_skippable();
+        match answer {
+            Ok(answer) => return answer,
+            Err(_) => {
+                info!("Error with questionnaire, try again.");
+            }
+        }
+    }
+}
+
+fn prompt_card(hand: Hand, choices: &Choices, last_trick: Option<&Trick>) -> Option<Card> {
+    let help = last_trick
+        .map(ToString::to_string)
+        .into_iter()
+        .chain(
+            choices
+                .forbidden()
+                .map(|(card, reason)| format!("{card} : {reason}")),
+        )
+        .collect::<Vec<_>>()
+        .join(", ");
+    let message = format!(
+        "Which card do you choose from {} ? (ESC to cancel)",
+        hand.cards()
+    );
+    loop {
+        let cards: Vec<Card> = choices.legal().into_iter().collect();
+        let page_size = cards.len();
+        let mut select = Select::new(&message, cards).with_page_size(page_size);
+        if !help.is_empty() {
+            select = select.with_help_message(&help);
+        }
+        match select.prompt_skippable() {
+            Ok(answer) => return answer,
+            Err(_) => {
+                info!("Error with questionnaire, try again.");
+            }
+        }
+    }
+}
+
+/// Rules of the table : the rules file if any, then the options given.
+fn rules(opts: &Opts) -> Result<RuleSet, BeloteErrorKind> {
+    let mut rules = match &opts.rules {
+        Some(path) => RuleSet::load(path)?,
+        None => RuleSet::default(),
+    };
+    if let Some(overtrump_on_partner) = opts.overtrump_on_partner {
+        rules.set_overtrump_on_partner(overtrump_on_partner);
+    }
+    if let Some(dealing) = opts.dealing {
+        rules.set_dealing(dealing);
+    }
+    if let Some(litige) = opts.litige {
+        rules.set_litige(litige);
+    }
+    if let Some(capot) = opts.capot {
+        rules.set_capot(capot);
+    }
+    if let Some(belote_on_failed_contract) = opts.belote_on_failed_contract {
+        rules.set_belote_on_failed_contract(belote_on_failed_contract);
+    }
+    if let Some(rounding) = opts.rounding {
+        rules.set_rounding(rounding);
+    }
+    Ok(rules)
+}
+
+/// Plays the match, asking humans between deals if they want to go on, `None` meaning it was interrupted.
+fn play(mut game_match: Match) -> Result<Option<Summary>, BeloteErrorKind> {
+    loop {
+        game_match = match game_match.play_deal_with(decide)? {
+            MatchState::Playing(game_match) => game_match,
+            MatchState::Ended(summary) => return Ok(Some(summary)),
+            MatchState::Interrupted => return Ok(None),
+        };
+
+        let game = game_match.game();
+        for team in Team::iter() {
+            info!(
+                "Game number {}, team {} = {} points",
+                game.number(),
+                team,
+                game.points()[team],
+            );
+        }
+
+        if !game.has_human() {
+            continue;
+        }
+        loop {
+            let answer = Confirm::new("Continue to play ? (ESC to cancel)")
+                .with_default(true)
+                .prompt_skippable();
+
+            match answer {
+                Ok(Some(true)) => break,
+                Ok(Some(false)) => return Ok(Some(game_match.end())),
+                Ok(None) => {
+                    info!("Interrupted.");
+                    return Ok(Some(game_match.end()));
+                }
+                Err(_) => {
+                    error!("Error with questionnaire, try again.");
+                }
+            }
+        }
+    }
+}
+
 fn main() -> Result<(), Box<dyn error::Error>> {
     color_eyre::install()?;
     let opts = Opts::parse();
     if opts.tracing {
         tracing_subscriber::fmt::init();
     }
+    if let Some(Command::Replay { file }) = opts.command {
+        let points = GameRecord::load(file)?.replay()?;
+        for team in Team::iter() {
+            println!("{team} = {} points", points[team]);
+        }
+        return Ok(());
+    }
+    let rules = rules(&opts)?;
+    info!("Rules: {rules:?}");
+    if let Some(Command::Solve {
+        north,
+        east,
+        south,
+        west,
+        trump,
+        leader,
+        trick,
+    }) = &opts.command
+    {
+        let mut hands = Hands::default();
+        for (position, cards) in [
+            (Position::North, north),
+            (Position::East, east),
+            (Position::South, south),
+            (Position::West, west),
+        ] {
+            for card in cards.split_whitespace() {
+                hands[position].take(Card::from_str(card)?)?;
+            }
+        }
+        let mut played = Vec::new();
+        let mut position = *leader;
+        for card in trick.split_whitespace() {
+            played.push((position, Card::from_str(card)?));
+            position = position.next();
+        }
+        let layout = Layout::new(hands, &played, *leader, *trump, rules)?;
+        let solution = solver::solve(&layout)?;
+        for team in Team::iter() {
+            println!("{team} : {} points", solution.points()[team]);
+        }
+        let best: Vec<String> = solution.best().iter().map(ToString::to_string).collect();
+        println!("Best for {} : {}", layout.next(), best.join(" "));
+        for (card, value) in solution.values() {
+            println!("  {card} : {value} points");
+        }
+        return Ok(());
+    }
+    let seed = opts.seed.unwrap_or_else(rand::random);
+    info!("Seed: {seed}");
+    let seats = [opts.north, opts.south, opts.east, opts.west];
+    let random = Random::for_table(seats.iter().filter(|seat| **seat == Seat::Random).count());
+    let reproducible = opts.seed.is_some() || opts.test || opts.command.is_some();
+    let (pimc_samples, pimc_budget) = (
+        opts.pimc_samples,
+        opts.pimc_budget_ms
+            .filter(|_| !reproducible)
+            .map(Duration::from_millis),
+    );
+    let (ismcts_iterations, ismcts_exploration) = (opts.ismcts_iterations, opts.ismcts_exploration);
+    let agent = move |seat: Seat, random: Random| match seat {
+        Seat::Random => Player::from(random),
+        Seat::Human => Player::from(Terminal),
+        Seat::Heuristic => Player::from(Heuristic),
+        Seat::Pimc => Player::from(Pimc::new(pimc_samples, pimc_budget)),
+        Seat::Ismcts => Player::from(Ismcts::new(ismcts_iterations, ismcts_exploration)),
+    };
+    if let Some(Command::Arena {
+        agents,
+        deals,
+        duplicate,
+    }) = &opts.command
+    {
+        let entrants = agents
+            .iter()
+            .enumerate()
+            .map(|(index, bot)| {
+                let bot = *bot;
+                let name = format!("{bot:?}#{}", index + 1);
+                Entrant::new(name, move || agent(bot.into(), Random::for_table(2)))
+            })
+            .collect::<Result<_, _>>()?;
+        let mut arena = Arena::new(entrants, *deals);
+        arena.set_duplicate(*duplicate);
+        arena.set_seed(seed);
+        arena.set_variant(opts.variant);
+        arena.set_rules(rules);
+        let standings = arena.run()?;
+        for standing in &standings {
+            println!("{standing}");
+        }
+        if let Some(path) = &opts.report {
+            fs::write(path, serde_json::to_string_pretty(&standings)?)?;
+        }
+        return Ok(());
+    }
     if opts.test {
-        let mut children = vec![];
-        for _ in 0..opts.concurrency.get() {
-            let games = opts.games;
-            children.push(thread::spawn(move || {
-                if let Err(e) = helpers::test_game(games) {
-                    error!("{e}");
-                }
-            }));
+        let mut simulator = Simulator::new(opts.games, opts.concurrency);
+        simulator.set_seed(seed);
+        simulator.set_variant(opts.variant);
+        simulator.set_rules(rules);
+        if let Some(game_seed) = opts.rerun {
+            println!("{}", simulator.replay(game_seed)?);
+            return Ok(());
         }
-        for child in children {
-            let _ = child.join();
+        let simulation = simulator.run();
+        for failure in simulation.failures() {
+            error!(
+                "game {} failed, replay it with --test --rerun {} : {}",
+                failure.game(),
+                failure.seed(),
+                failure.error()
+            );
+        }
+        println!("{}", simulation.statistics());
+        if let Some(path) = &opts.report {
+            simulation.statistics().save(path)?;
         }
     } else {
+        let seat = |seat: Seat| agent(seat, random);
         let players = Players::new(
-            Player::new(!opts.human_north),
-            Player::new(!opts.human_south),
-            Player::new(!opts.human_east),
-            Player::new(!opts.human_west),
+            seat(opts.north),
+            seat(opts.south),
+            seat(opts.east),
+            seat(opts.west),
         );
 
+        let mut rng = StdRng::seed_from_u64(seed);
         let order = if opts.random_order {
-            Order::random()
+            Order::random(&mut rng)
         } else {
             Order::default()
         };
 
-        let mut game = Game::default(players, order);
-        'current_game: for _ in 0..opts.games {
-            let distribution = game.distribute()?;
-            let bidding = distribution.bidding()?;
-            game = match bidding.playing_game_or_redistribute()? {
-                PlayOrNext::NextGame(next_game) => next_game,
-                PlayOrNext::PlayGame(in_game) => match in_game.play()? {
-                    NextGameOrInterrupt::NextGame(next_game) => next_game,
-                    NextGameOrInterrupt::Interrupted => break 'current_game,
-                },
-                PlayOrNext::Interrupted => break 'current_game,
-            };
-
-            for team in Team::iter() {
-                info!(
-                    "Game number {}, team {} = {} points",
-                    game.number(),
-                    team,
-                    game.points()[team],
-                );
-            }
-
-            if game.is_full_random() {
-                continue;
-            }
-            loop {
-                let answer = Confirm::new("Continue to play ? (ESC to cancel)")
-                    .with_default(true)
-                    .prompt_skippable();
-
-                match answer {
-                    Ok(Some(true)) => {
-                        continue 'current_game;
-                    }
-                    Ok(Some(false)) => {
-                        break 'current_game;
-                    }
-                    Ok(None) => {
-                        info!("Interrupted.");
-                        break 'current_game;
-                    }
-                    Err(_) => {
-                        error!("Error with questionnaire, try again.");
-                    }
-                };
-            }
+        let mut game = Game::default(players, order, rng);
+        game.set_variant(opts.variant);
+        game.set_rules(rules);
+        let recorder = Recorder::new(game.players());
+        if opts.record.is_some() {
+            game.observe(recorder.clone());
+        }
+        let goal = opts.target.map_or(Goal::Deals(opts.games), Goal::Target);
+        if let Some(summary) = play(Match::new(game, goal))? {
+            println!("{summary}");
+        }
+        if let Some(path) = opts.record {
+            recorder.record().save(&path)?;
+            info!("Game recorded into {}", path.display());
         }
         info!("GAME ENDED");
     }
@@ -0,0 +1,252 @@
+use crate::engine::{Action, Engine};
+use crate::errors::BeloteErrorKind;
+use crate::event::{GameEvent, Observer};
+use crate::game::Game;
+use crate::initial::Initial;
+use crate::playing::Outcome;
+use crate::points::Points;
+use crate::position::Position;
+use crate::team::Team;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use strum::IntoEnumIterator;
+
+/// When a match is over.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Goal {
+    /// A fixed number of deals, redistributions included.
+    Deals(u64),
+    /// First team reaching this score, usually 501, 1000 or 2000.
+    Target(u64),
+}
+
+impl Default for Goal {
+    fn default() -> Self {
+        Self::Target(1000)
+    }
+}
+
+/// Final result of a match.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Summary {
+    deals: u64,
+    points: Points,
+    winner: Option<Team>,
+}
+
+impl Summary {
+    pub const fn deals(&self) -> u64 {
+        self.deals
+    }
+    pub const fn points(&self) -> Points {
+        self.points
+    }
+    /// Winning team, `None` for a draw.
+    pub const fn winner(&self) -> Option<Team> {
+        self.winner
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.winner {
+            Some(winner) => write!(
+                f,
+                "{winner} wins with {} points against {} after {} deals",
+                self.points[winner],
+                self.points[winner.other()],
+                self.deals
+            ),
+            None => write!(
+                f,
+                "Draw at {} points after {} deals",
+                self.points[Team::NorthSouth],
+                self.deals
+            ),
+        }
+    }
+}
+
+/// State of a match after a deal.
+#[allow(clippy::large_enum_variant)]
+pub enum MatchState {
+    Playing(Match),
+    Ended(Summary),
+    Interrupted,
+}
+
+/// Taker and outcome of the last scored deal, `None` after a redistribution.
+#[derive(Clone, Debug, Default)]
+struct LastDeal(Arc<Mutex<Option<(Position, Outcome)>>>);
+
+impl LastDeal {
+    fn get(&self) -> Option<(Position, Outcome)> {
+        self.0.lock().ok().and_then(|last_deal| *last_deal)
+    }
+}
+
+impl Observer for LastDeal {
+    fn notify(&mut self, event: &GameEvent) {
+        let Ok(mut last_deal) = self.0.lock() else {
+            return;
+        };
+        match *event {
+            GameEvent::DealStarted { .. } => *last_deal = None,
+            GameEvent::DealScored { taker, outcome, .. } => *last_deal = Some((taker, outcome)),
+            _ => {}
+        }
+    }
+}
+
+/// Deals played one after the other until the [`Goal`] is reached.
+#[derive(Debug)]
+pub struct Match {
+    game: Game<Initial>,
+    goal: Goal,
+    deals: u64,
+    last_deal: LastDeal,
+}
+
+impl Match {
+    pub fn new(mut game: Game<Initial>, goal: Goal) -> Self {
+        let last_deal = LastDeal::default();
+        game.observe(last_deal.clone());
+        Self {
+            game,
+            goal,
+            deals: 0,
+            last_deal,
+        }
+    }
+
+    pub const fn game(&self) -> &Game<Initial> {
+        &self.game
+    }
+
+    pub const fn goal(&self) -> Goal {
+        self.goal
+    }
+
+    pub const fn deals(&self) -> u64 {
+        self.deals
+    }
+
+    /// Plays the next deal, then checks if the match is over.
+    pub fn play_deal(self) -> Result<MatchState, BeloteErrorKind> {
+        self.play_deal_with(Engine::ask)
+    }
+
+    /// Plays the next deal, every decision being answered by `decide`, then checks if the match
+    /// is over.
+    pub fn play_deal_with(
+        mut self,
+        decide: impl FnMut(&mut Engine) -> Option<Action>,
+    ) -> Result<MatchState, BeloteErrorKind> {
+        let Some(game) = self.game.play_deal_with(decide)? else {
+            return Ok(MatchState::Interrupted);
+        };
+        self.game = game;
+        self.deals += 1;
+        Ok(if self.is_over() {
+            MatchState::Ended(self.end())
+        } else {
+            MatchState::Playing(self)
+        })
+    }
+
+    /// Plays deals until the match is over, `None` meaning it was interrupted.
+    pub fn play(mut self) -> Result<Option<Summary>, BeloteErrorKind> {
+        loop {
+            self = match self.play_deal()? {
+                MatchState::Playing(game_match) => game_match,
+                MatchState::Ended(summary) => return Ok(Some(summary)),
+                MatchState::Interrupted => return Ok(None),
+            };
+        }
+    }
+
+    /// Stops the match now, publishing its winner.
+    pub fn end(self) -> Summary {
+        let winner = self.winner();
+        let deals = self.deals;
+        let points = self.game.end(winner);
+        Summary {
+            deals,
+            points,
+            winner,
+        }
+    }
+
+    fn leader(points: Points) -> Option<Team> {
+        let north_south = points[Team::NorthSouth];
+        let west_east = points[Team::WestEast];
+        match north_south.cmp(&west_east) {
+            std::cmp::Ordering::Greater => Some(Team::NorthSouth),
+            std::cmp::Ordering::Less => Some(Team::WestEast),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    fn is_over(&self) -> bool {
+        let points = self.game.points();
+        match self.goal {
+            Goal::Deals(deals) => self.deals >= deals,
+            Goal::Target(target) => Team::iter().any(|team| points[team] >= target),
+        }
+    }
+
+    /// Team with the most points, unless both teams broke the target during the last deal :
+    /// then the taker team wins only if it fulfilled its own contract.
+    fn winner(&self) -> Option<Team> {
+        let points = self.game.points();
+        let Goal::Target(target) = self.goal else {
+            return Self::leader(points);
+        };
+        if Team::iter().all(|team| points[team] >= target) {
+            if let Some((taker, outcome)) = self.last_deal.get() {
+                return if outcome.is_made() {
+                    Some(taker.team())
+                } else {
+                    Some(taker.team().other())
+                };
+            }
+        }
+        Self::leader(points)
+    }
+}
+
+#[test]
+fn match_tests() {
+    use crate::agent::Random;
+    use crate::order::Order;
+    use crate::player::Player;
+    use crate::players::Players;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let new_game = |seed| {
+        let random = Random::for_table(4);
+        let players = Players::new(
+            Player::from(random),
+            Player::from(random),
+            Player::from(random),
+            Player::from(random),
+        );
+        Game::default(players, Order::default(), StdRng::seed_from_u64(seed))
+    };
+
+    let Ok(Some(summary)) = Match::new(new_game(1), Goal::Deals(5)).play() else {
+        panic!("match should end");
+    };
+    assert_eq!(summary.deals(), 5);
+
+    for seed in 0..20 {
+        let Ok(Some(summary)) = Match::new(new_game(seed), Goal::Target(501)).play() else {
+            panic!("match should end");
+        };
+        let Some(winner) = summary.winner() else {
+            panic!("a match to a target always has a winner");
+        };
+        assert!(summary.points()[winner] >= 501);
+    }
+}
@@ -1,11 +1,13 @@
 use crate::constants;
 use crate::position::Position;
 use derive_more::{Index, IntoIterator};
-use rand::{thread_rng, Rng};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use strum::{EnumCount, VariantArray};
 
-#[derive(Debug, IntoIterator, Index, Clone, Copy)]
+/// Order in which players are dealt and bid, the first one leads the first trick.
+#[derive(Debug, IntoIterator, Index, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Order([Position; constants::MAX_PLAYERS]);
 
 impl Default for Order {
@@ -30,8 +32,7 @@ impl fmt::Display for Order {
 }
 
 impl Order {
-    pub fn random() -> Self {
-        let mut rng = thread_rng();
+    pub fn random(rng: &mut impl Rng) -> Self {
         let mut variants = [
             Position::North,
             Position::North,
@@ -43,10 +44,18 @@ impl Order {
         variants.rotate_left(random_index);
         Self(variants)
     }
+    /// Order of the table starting from `first`.
+    pub fn starting(first: Position) -> Self {
+        let mut order = Self::default();
+        while order.first() != first {
+            order.rotate();
+        }
+        order
+    }
     pub const fn first(&self) -> Position {
         self.0[0]
     }
-    pub fn rotate(&mut self) {
+    pub const fn rotate(&mut self) {
         self.0.rotate_left(1);
     }
 }
@@ -0,0 +1,246 @@
+use crate::agent::Agent;
+use crate::announce::Announce;
+use crate::bidding::Bid;
+use crate::card::{Card, Trump};
+use crate::coinche::Bids;
+use crate::heuristic::Heuristic;
+use crate::solver::{Layout, Solver};
+use crate::tracker::Tracker;
+use crate::view::PlayerView;
+use rand::RngCore;
+use std::time::{Duration, Instant};
+
+/// Agent playing by Perfect Information Monte Carlo.
+///
+/// The unseen cards are dealt again many times, consistently with what the [`Tracker`] saw.
+/// Each deal is solved double dummy and the card with the best average is played.
+/// Bidding is left to the [`Heuristic`].
+///
+/// Without a time budget the agent only counts its deals, so a seeded game plays the same
+/// cards on any machine. A budget makes it depend on the machine load instead.
+#[derive(Debug, Clone)]
+pub struct Pimc {
+    samples: usize,
+    budget: Option<Duration>,
+    heuristic: Heuristic,
+}
+
+impl Default for Pimc {
+    fn default() -> Self {
+        Self::new(20, None)
+    }
+}
+
+impl Pimc {
+    /// Agent solving at most `samples` deals for each card, stopping once `budget`, if any, is
+    /// spent. At least one deal is always solved.
+    pub const fn new(samples: usize, budget: Option<Duration>) -> Self {
+        Self {
+            samples,
+            budget,
+            heuristic: Heuristic,
+        }
+    }
+
+    pub const fn samples(&self) -> usize {
+        self.samples
+    }
+    pub const fn budget(&self) -> Option<Duration> {
+        self.budget
+    }
+}
+
+impl Agent for Pimc {
+    fn take(
+        &mut self,
+        view: &PlayerView,
+        card_returned: Card,
+        rng: &mut dyn RngCore,
+    ) -> Option<bool> {
+        self.heuristic.take(view, card_returned, rng)
+    }
+
+    fn choose_color(
+        &mut self,
+        view: &PlayerView,
+        card_returned: Card,
+        rng: &mut dyn RngCore,
+    ) -> Option<Option<Trump>> {
+        self.heuristic.choose_color(view, card_returned, rng)
+    }
+
+    fn bid(&mut self, view: &PlayerView, bids: &Bids, rng: &mut dyn RngCore) -> Option<Bid> {
+        self.heuristic.bid(view, bids, rng)
+    }
+
+    fn declare(
+        &mut self,
+        view: &PlayerView,
+        announces: &[Announce],
+        rng: &mut dyn RngCore,
+    ) -> Option<Vec<Announce>> {
+        self.heuristic.declare(view, announces, rng)
+    }
+
+    fn declare_belote(
+        &mut self,
+        view: &PlayerView,
+        card: Card,
+        rng: &mut dyn RngCore,
+    ) -> Option<bool> {
+        self.heuristic.declare_belote(view, card, rng)
+    }
+
+    fn choose_card(
+        &mut self,
+        view: &PlayerView,
+        choices: &[Card],
+        rng: &mut dyn RngCore,
+    ) -> Option<Card> {
+        let tracker = Tracker::from_view(view);
+        let position = view.position();
+        let Some(trump) = tracker.trump() else {
+            return choices.first().copied();
+        };
+        if choices.len() < 2 {
+            return choices.first().copied();
+        }
+        let leader = tracker.leader(position);
+        let start = Instant::now();
+        let mut solver = Solver::new();
+        let mut totals: Vec<(Card, u64)> = choices.iter().map(|card| (*card, 0)).collect();
+        for sample in 0..self.samples.max(1) {
+            if sample > 0 && self.budget.is_some_and(|budget| start.elapsed() >= budget) {
+                break;
+            }
+            let Some(hands) = tracker.sample(position, view.hand().cards(), rng) else {
+                break;
+            };
+            let Ok(layout) = Layout::new(hands, tracker.trick(), leader, trump, tracker.rules())
+            else {
+                break;
+            };
+            let Ok(values) = solver.card_values(&layout) else {
+                break;
+            };
+            for (card, value) in values {
+                if let Some((_, total)) = totals.iter_mut().find(|(choice, _)| *choice == card) {
+                    *total += value;
+                }
+            }
+        }
+        totals
+            .iter()
+            .max_by_key(|(_, total)| *total)
+            .map(|(card, _)| *card)
+    }
+}
+
+#[test]
+fn pimc_tests() {
+    use crate::card::Color;
+    use crate::constants::MAX_CARDS_BY_PLAYER;
+    use crate::event::{GameEvent, Observer};
+    use crate::hands::Hands;
+    use crate::position::Position;
+    use crate::rules::RuleSet;
+    use crate::stack::Stack;
+    use crate::trick::Trick;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use strum::IntoEnumIterator;
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let trump = Trump::Color(Color::Heart);
+    let mut pimc = Pimc::new(4, None);
+    let mut events = Vec::new();
+    let mut stack = Stack::random(&mut rng);
+    events.push(GameEvent::DealStarted {
+        number: 0,
+        order: crate::order::Order::default(),
+        stack,
+        litige: 0,
+        variant: crate::variant::Variant::default(),
+        rules: RuleSet::default(),
+    });
+    events.push(GameEvent::TrumpChosen {
+        taker: Position::North,
+        trump,
+    });
+    let mut hands = Hands::default();
+    for position in Position::iter() {
+        for _ in 0..MAX_CARDS_BY_PLAYER {
+            let Ok(card) = stack.give_card() else {
+                panic!("stack should have 32 cards");
+            };
+            let _ = hands[position].take(card);
+        }
+    }
+
+    // The first 5 tricks are played with the first legal card, the sixth by the agent.
+    let Ok(mut layout) = Layout::new(hands, &[], Position::North, trump, RuleSet::default()) else {
+        panic!("layout should be valid");
+    };
+    let mut trick = Vec::new();
+    let mut won_tricks = 0;
+    while Position::iter().any(|position| layout.hands()[position].len() > 3) {
+        let position = layout.next();
+        let Some(card) = layout
+            .choices()
+            .ok()
+            .and_then(|choices| choices.into_iter().next())
+        else {
+            panic!("{position} should have a card to play");
+        };
+        events.push(GameEvent::CardPlayed { position, card });
+        trick.push((position, card));
+        let Ok((next, won)) = layout.play(card) else {
+            panic!("{card} should be legal");
+        };
+        if let (Some((winner, points)), Ok(cards)) = (won, <[_; 4]>::try_from(trick.as_slice())) {
+            let number = won_tricks + 1;
+            events.push(GameEvent::TrickWon {
+                trick: Trick::new(number, cards, winner),
+                points,
+            });
+            won_tricks = number;
+            trick.clear();
+        }
+        layout = next;
+    }
+
+    let position = layout.next();
+    let hand = layout.hands()[position];
+    // The agent only sees the deal through the view of its seat.
+    let mut view = PlayerView::new(position);
+    for event in &events {
+        view.notify(event);
+    }
+    view.set_hand(hand);
+    let tracker = Tracker::from_view(&view);
+    for seat in Position::iter() {
+        let possible = tracker.possible(seat);
+        assert!(layout.hands()[seat]
+            .into_iter()
+            .all(|card| possible.contains(card)));
+    }
+    for _ in 0..10 {
+        let Some(sample) = tracker.sample(position, hand.cards(), &mut rng) else {
+            panic!("a deal should be found");
+        };
+        for seat in Position::iter() {
+            assert_eq!(sample[seat].len(), 3);
+            let possible = tracker.possible(seat);
+            assert!(
+                seat == position || sample[seat].into_iter().all(|card| possible.contains(card))
+            );
+        }
+        assert_eq!(sample[position], hand);
+    }
+    let Ok(choices) = layout.choices() else {
+        panic!("{position} should have choices");
+    };
+    let choices: Vec<Card> = choices.into_iter().collect();
+    let chosen = pimc.choose_card(&view, &choices, &mut rng);
+    assert!(chosen.is_some_and(|card| choices.contains(&card)));
+}
@@ -1,37 +1,72 @@
-use crate::card::{Card, Color};
+use crate::agent::Agent;
+use crate::card::{Card, Color, Trump};
+use crate::card_set::CardSet;
 use crate::constants::MAX_CARDS_BY_PLAYER;
 use crate::errors::BeloteErrorKind;
 use crate::hand::Hand;
 use crate::position::Position;
+use crate::rules::RuleSet;
 use crate::turn::Turn;
+use crate::view::PlayerView;
 use std::fmt;
 use tracing::info;
 
-#[derive(Clone, Copy, Debug, Default)]
+/// A seat at the table, decisions being delegated to its [`Agent`] which only sees the
+/// [`PlayerView`] of the seat.
+#[derive(Debug)]
 pub struct Player {
-    random: bool,
+    agent: Box<dyn Agent>,
+    view: PlayerView,
 }
 
 impl fmt::Display for Player {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "random: {}", self.random)
+        write!(f, "{:?}", self.agent)
+    }
+}
+
+impl<A: Agent + 'static> From<A> for Player {
+    fn from(agent: A) -> Self {
+        Self::new(Box::new(agent))
     }
 }
 
 impl Player {
-    pub const fn new(random: bool) -> Self {
-        Self { random }
+    pub fn new(agent: Box<dyn Agent>) -> Self {
+        Self {
+            agent,
+            view: PlayerView::new(Position::North),
+        }
+    }
+    /// Sits the player at `position`.
+    pub fn sit(&mut self, position: Position) {
+        self.view = PlayerView::new(position);
+    }
+    pub fn agent(&self) -> &dyn Agent {
+        self.agent.as_ref()
+    }
+    pub fn agent_mut(&mut self) -> &mut dyn Agent {
+        self.agent.as_mut()
     }
-    pub const fn random(&self) -> bool {
-        self.random
+    pub const fn view(&self) -> &PlayerView {
+        &self.view
     }
+    pub const fn view_mut(&mut self) -> &mut PlayerView {
+        &mut self.view
+    }
+    /// Borrows the agent and the view it decides from at once.
+    pub fn agent_and_view(&mut self) -> (&mut dyn Agent, &PlayerView) {
+        (self.agent.as_mut(), &self.view)
+    }
+    /// Legal cards of `hand` for the current `turn`, with the rule which made them legal and
+    /// why every other card is forbidden.
     pub fn choices(
-        &self,
         hand: &Hand,
         position: &Position,
         turn: &Turn,
-        trump_color: Color,
-    ) -> Result<Vec<Card>, BeloteErrorKind> {
+        trump: Trump,
+        rules: &RuleSet,
+    ) -> Result<Choices, BeloteErrorKind> {
         if hand.len() as u64 != MAX_CARDS_BY_PLAYER as u64 - turn.number() + 1 {
             return Err(BeloteErrorKind::InvalidCase(format!(
                 "Bad number of cards for player {position} : {} it should be {}",
@@ -39,73 +74,49 @@ impl Player {
                 MAX_CARDS_BY_PLAYER as u64 - turn.number() + 1
             )));
         }
-        info!("{position} : trump color is {trump_color}");
+        info!("{position} : trump is {trump}");
         info!(
             "called color {:?} : master card {:?}",
             turn.called_color(),
             turn.master_card()
         );
-        let choices = match (turn.called_color(), turn.master_card()) {
-            (None, None) => hand.into_iter().flatten().collect(),
+        let hand = hand.cards();
+        let (reason, legal) = match (turn.called_color(), turn.master_card()) {
+            (None, None) => (LegalityReason::Lead, hand),
             (Some(called_color), Some(master_card)) => {
-                let mut trumps = Vec::new();
-                let mut trumps_less = Vec::new();
-                let mut trumps_more = Vec::new();
-                let mut other_colors = Vec::new();
-                let mut same_colors = Vec::new();
-
-                for card in hand.into_iter().flatten() {
-                    if card.color() == trump_color {
-                        trumps.push(card);
-                        if card.power(trump_color) > master_card.power(trump_color) {
-                            trumps_more.push(card);
-                        } else {
-                            trumps_less.push(card);
-                        }
-                    } else if card.color() == called_color {
-                        same_colors.push(card);
-                    } else {
-                        other_colors.push(card);
-                    }
-                }
+                let same_colors = hand.of_color(called_color);
+                let trumps = (hand & CardSet::trumps(trump)) - same_colors;
+                let other_colors = hand - same_colors - trumps;
+                let beating = CardSet::beating(master_card, trump);
+                let trumps_more = trumps & beating;
 
-                // info!("trumps: {:?}", trumps);
-                // info!("trumps_less: {:?}", trumps_less);
-                // info!("trumps_more: {:?}", trumps_more);
-                // info!("other_colors: {:?}", other_colors);
-                // info!("same_colors: {:?}", same_colors);
-
-                if called_color == trump_color {
-                    if !trumps_more.is_empty() {
-                        info!("{position} : trump color asked, I must go up");
-                        trumps_more
-                    } else if !trumps_less.is_empty() {
-                        info!("{position} : trump color asked, but can't go up, going down");
-                        trumps_less
+                if trump.is_trump(called_color) {
+                    let same_more = same_colors & beating;
+                    if !same_more.is_empty() {
+                        (LegalityReason::GoUp, same_more)
+                    } else if !same_colors.is_empty() {
+                        (LegalityReason::CannotGoUp, same_colors)
                     } else {
-                        info!("{position} : no trump left I must piss");
-                        other_colors
+                        (LegalityReason::Discard, other_colors | trumps)
                     }
                 } else if !same_colors.is_empty() {
-                    info!("{position} : I have asked color");
-                    same_colors
-                } else if turn.master_team() == position.team() {
-                    info!(
-                        "{position} : my team ({}) is master, I can defausse or cut",
-                        turn.master_team()
-                    );
+                    (LegalityReason::Follow, same_colors)
+                } else if turn.master_team() == position.team() && !rules.overtrump_on_partner() {
                     if trumps_more.is_empty() {
-                        other_colors.extend(trumps_less);
+                        (LegalityReason::PartnerMaster, other_colors | trumps)
+                    } else {
+                        (LegalityReason::PartnerMaster, other_colors | trumps_more)
+                    }
+                } else if !trumps_more.is_empty() {
+                    if trump.is_trump(master_card.color()) {
+                        (LegalityReason::Overtrump, trumps_more)
                     } else {
-                        other_colors.extend(trumps_more);
+                        (LegalityReason::Cut, trumps_more)
                     }
-                    other_colors
                 } else if !trumps.is_empty() {
-                    info!("{position} : I can't give asked color, I must cut with a trump");
-                    trumps
+                    (LegalityReason::Piss, other_colors | trumps)
                 } else {
-                    info!("{position} : no trumps left, I must piss");
-                    other_colors
+                    (LegalityReason::Discard, other_colors)
                 }
             }
             _ => {
@@ -114,6 +125,194 @@ impl Player {
                 )))
             }
         };
-        Ok(choices)
+        info!("{position} : {reason}");
+        Ok(Choices {
+            reason,
+            legal,
+            hand,
+            called_color: turn.called_color(),
+        })
+    }
+}
+
+/// Cards a player may play, the rule deciding them and why the other cards of its hand are forbidden.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Choices {
+    reason: LegalityReason,
+    legal: CardSet,
+    hand: CardSet,
+    called_color: Option<Color>,
+}
+
+impl Choices {
+    pub const fn reason(&self) -> LegalityReason {
+        self.reason
+    }
+    pub const fn legal(&self) -> CardSet {
+        self.legal
+    }
+    /// Cards of the hand which cannot be played, with the reason.
+    pub fn forbidden(&self) -> impl Iterator<Item = (Card, ForbiddenReason)> + '_ {
+        (self.hand - self.legal)
+            .into_iter()
+            .filter_map(|card| self.why_forbidden(&card).map(|reason| (card, reason)))
+    }
+    pub const fn contains(&self, card: &Card) -> bool {
+        self.legal.contains(*card)
+    }
+    pub const fn is_empty(&self) -> bool {
+        self.legal.is_empty()
+    }
+    /// Why `card` cannot be played, `None` if it is legal or not held.
+    pub fn why_forbidden(&self, card: &Card) -> Option<ForbiddenReason> {
+        if !self.hand.contains(*card) || self.legal.contains(*card) {
+            return None;
+        }
+        match self.reason {
+            LegalityReason::Lead | LegalityReason::Piss | LegalityReason::Discard => None,
+            LegalityReason::GoUp if self.called_color == Some(card.color()) => {
+                Some(ForbiddenReason::MustGoUp)
+            }
+            LegalityReason::Follow | LegalityReason::GoUp | LegalityReason::CannotGoUp => {
+                Some(ForbiddenReason::MustFollow)
+            }
+            LegalityReason::PartnerMaster => Some(ForbiddenReason::MustGoUp),
+            LegalityReason::Cut => Some(ForbiddenReason::MustCut),
+            LegalityReason::Overtrump => Some(ForbiddenReason::MustOvertrump),
+        }
+    }
+}
+
+/// Rule of the trick deciding which cards a player may play.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LegalityReason {
+    /// First card of the trick, anything goes.
+    Lead,
+    /// The called color must be followed.
+    Follow,
+    /// Trumps are called : a higher trump must be played when possible.
+    GoUp,
+    /// Trumps are called but none is high enough, any trump of the called color.
+    CannotGoUp,
+    /// The partner is master : discarding is allowed, or cutting, over its trump when possible.
+    PartnerMaster,
+    /// The called color is missing : nobody cut yet, any trump must be played.
+    Cut,
+    /// The called color is missing and an opponent cut : a higher trump must be played.
+    Overtrump,
+    /// An opponent cut higher than every trump held : any card may be played (pisser).
+    Piss,
+    /// Neither the called color nor trumps are held : any card may be played.
+    Discard,
+}
+
+/// Why a card of the hand cannot be played.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ForbiddenReason {
+    /// A card of the called color is held.
+    MustFollow,
+    /// A higher trump is held.
+    MustGoUp,
+    /// The called color is missing, a trump is held and must be played.
+    MustCut,
+    /// An opponent cut, a higher trump is held and must be played.
+    MustOvertrump,
+}
+
+impl fmt::Display for ForbiddenReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MustFollow => write!(f, "the called color must be followed"),
+            Self::MustGoUp => write!(f, "a higher trump must be played"),
+            Self::MustCut => write!(f, "a trump must be played"),
+            Self::MustOvertrump => write!(f, "the cut of the opponent must be overtrumped"),
+        }
+    }
+}
+
+impl fmt::Display for LegalityReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Lead => write!(f, "first to play, any card"),
+            Self::Follow => write!(f, "must follow the called color"),
+            Self::GoUp => write!(f, "trump color asked, must go up"),
+            Self::CannotGoUp => write!(f, "trump color asked, cannot go up"),
+            Self::PartnerMaster => write!(f, "partner is master, can discard or cut"),
+            Self::Cut => write!(f, "cannot follow, must cut with a trump"),
+            Self::Overtrump => write!(f, "an opponent cut, must overtrump"),
+            Self::Piss => write!(f, "cannot overtrump, can piss"),
+            Self::Discard => write!(f, "neither called color nor trumps, can discard"),
+        }
+    }
+}
+
+#[test]
+fn player_tests() {
+    use crate::order::Order;
+    use std::str::FromStr;
+
+    let cards = |cards: &[&str]| {
+        cards
+            .iter()
+            .filter_map(|card| Card::from_str(card).ok())
+            .collect::<CardSet>()
+    };
+    let card = |card: &str| Card::from_str(card).unwrap_or_else(|_| panic!("bad card {card}"));
+    let trump = Trump::Color(Color::Heart);
+    let rules = RuleSet::default();
+    let mut hand = Hand::default();
+    for card in cards(&["♥7", "♥J", "♣8", "♦10", "♦K", "♦Q", "♦J", "♦9"]) {
+        let _ = hand.take(card);
+    }
+
+    // North leads a spade, East cuts with the heart 9, South has no spade.
+    let mut turn = Turn::new(1, Order::default());
+    for (position, card) in [Position::North, Position::East]
+        .into_iter()
+        .zip(["♠11", "♥9"].map(card))
+    {
+        turn.put(trump, position, &card);
+    }
+    let Ok(choices) = Player::choices(&hand, &Position::South, &turn, trump, &rules) else {
+        panic!("south should be able to play");
+    };
+    assert_eq!(choices.reason(), LegalityReason::Overtrump);
+    assert_eq!(choices.legal(), cards(&["♥J"]));
+    assert_eq!(
+        choices.why_forbidden(&card("♥7")),
+        Some(ForbiddenReason::MustOvertrump)
+    );
+    assert_eq!(choices.forbidden().count(), 7);
+
+    // Without the heart jack, South cannot overtrump and may play anything.
+    let _ = hand.give(&card("♥J"));
+    let _ = hand.take(card("♣7"));
+    let Ok(choices) = Player::choices(&hand, &Position::South, &turn, trump, &rules) else {
+        panic!("south should be able to play");
+    };
+    assert_eq!(choices.reason(), LegalityReason::Piss);
+    assert_eq!(choices.legal().len(), 8);
+    assert!(choices.forbidden().next().is_none());
+
+    // Tout-atout, West leads the spade 10 : a lower spade must go up, another color follow.
+    let mut hand = Hand::default();
+    for card in cards(&["♠7", "♠J", "♥8", "♦10", "♦K", "♣Q", "♣J", "♣9"]) {
+        let _ = hand.take(card);
     }
+    let mut turn = Turn::new(1, Order::starting(Position::West));
+    turn.put(Trump::AllTrump, Position::West, &card("♠10"));
+    let Ok(choices) = Player::choices(&hand, &Position::North, &turn, Trump::AllTrump, &rules)
+    else {
+        panic!("north should be able to play");
+    };
+    assert_eq!(choices.reason(), LegalityReason::GoUp);
+    assert_eq!(choices.legal(), cards(&["♠J"]));
+    assert_eq!(
+        choices.why_forbidden(&card("♠7")),
+        Some(ForbiddenReason::MustGoUp)
+    );
+    assert_eq!(
+        choices.why_forbidden(&card("♥8")),
+        Some(ForbiddenReason::MustFollow)
+    );
 }
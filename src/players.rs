@@ -1,8 +1,9 @@
 use crate::player::Player;
 use crate::position::Position;
-use std::ops::Index;
+use std::ops::{Index, IndexMut};
 
-#[derive(Debug, Copy, Clone)]
+/// The 4 seats, indexed by [`Position`].
+#[derive(Debug)]
 pub struct Players {
     north: Player,
     east: Player,
@@ -11,7 +12,11 @@ pub struct Players {
 }
 
 impl Players {
-    pub const fn new(north: Player, south: Player, east: Player, west: Player) -> Self {
+    pub fn new(mut north: Player, mut south: Player, mut east: Player, mut west: Player) -> Self {
+        north.sit(Position::North);
+        south.sit(Position::South);
+        east.sit(Position::East);
+        west.sit(Position::West);
         Self {
             north,
             east,
@@ -20,20 +25,11 @@ impl Players {
         }
     }
 
-    pub const fn full_random(&self) -> bool {
-        self.north.random() && self.south.random() && self.east.random() && self.west.random()
-    }
-
-    pub fn randomization(&self) -> f64 {
-        let random_players = f64::from(self.north.random())
-            + f64::from(self.east.random())
-            + f64::from(self.south.random())
-            + f64::from(self.west.random());
-        if random_players.is_normal() {
-            1.0 / random_players
-        } else {
-            0.0
-        }
+    pub fn has_human(&self) -> bool {
+        self.north.agent().is_human()
+            || self.south.agent().is_human()
+            || self.east.agent().is_human()
+            || self.west.agent().is_human()
     }
 }
 
@@ -49,3 +45,14 @@ impl Index<Position> for Players {
         }
     }
 }
+
+impl IndexMut<Position> for Players {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        match position {
+            Position::North => &mut self.north,
+            Position::South => &mut self.south,
+            Position::East => &mut self.east,
+            Position::West => &mut self.west,
+        }
+    }
+}
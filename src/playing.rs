@@ -1,32 +1,94 @@
-use crate::card::Card;
-use crate::card::Color;
+use crate::announce::{self, Announce};
+use crate::card::{Card, Trump, Value};
+use crate::coinche::{Level, Taken};
 use crate::constants::{MAX_CARDS_BY_PLAYER, MAX_PLAYERS};
+use crate::engine::{Action, Decision, Step};
 use crate::errors::BeloteErrorKind;
+use crate::event::GameEvent;
 use crate::game::Game;
 use crate::hand::Hand;
 use crate::hands::Hands;
 use crate::initial::Initial;
+use crate::order::Order;
+use crate::player::Player;
+use crate::points::Points;
 use crate::position::Position;
+use crate::rules::{Litige, RuleSet};
 use crate::team::Team;
+use crate::trick::Trick;
 use crate::turn::Turn;
-use derive_more::{Constructor, Deref, DerefMut};
-use inquire::Select;
-use rand::seq::IteratorRandom;
-use std::str::FromStr;
+use derive_more::{Deref, DerefMut};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
 use tracing::{info, warn};
 
-#[derive(Constructor, Deref, DerefMut)]
+/// Phase where the 8 tricks of a deal are played.
+#[derive(Deref, DerefMut)]
 pub struct Playing {
     taker: Position,
     hands: Hands,
-    trump_color: Color,
+    trump: Trump,
+    taken: Option<Taken>,
+    turn: Turn,
+    /// Tricks won so far, in order.
+    tricks: Vec<Trick>,
+    /// Seat to play next.
+    current_position: Position,
+    /// Card chosen by the belote holder, waiting for it to announce belote or not.
+    chosen_card: Option<Card>,
+    /// Seats asked for their announces during the first trick.
+    asked: Vec<Position>,
+    declared: Vec<(Position, Announce)>,
+    belote_holder: Option<Position>,
+    belote_declared: bool,
+    belote_rebelote: Option<Team>,
+    attack_points: u64,
+    defense_points: u64,
+    attack_tricks: usize,
+    taker_tricks: usize,
     #[deref]
     #[deref_mut]
     initial: Initial,
 }
 
 impl Playing {
-    pub const fn into(self) -> Initial {
+    #[allow(clippy::similar_names)]
+    pub fn new(
+        taker: Position,
+        hands: Hands,
+        trump: Trump,
+        taken: Option<Taken>,
+        initial: Initial,
+    ) -> Self {
+        let order = initial.order();
+        let belote_holder = trump.color().and_then(|trump_color| {
+            order
+                .into_iter()
+                .find(|position| hands[*position].belote_rebelote(trump_color))
+        });
+        Self {
+            taker,
+            hands,
+            trump,
+            taken,
+            turn: Turn::new(1, order),
+            tricks: Vec::new(),
+            current_position: order.first(),
+            chosen_card: None,
+            asked: Vec::new(),
+            declared: Vec::new(),
+            belote_holder,
+            belote_declared: false,
+            belote_rebelote: None,
+            attack_points: 0,
+            defense_points: 0,
+            attack_tricks: 0,
+            taker_tricks: 0,
+            initial,
+        }
+    }
+
+    pub fn into(self) -> Initial {
         self.initial
     }
     pub fn hand(&self, position: Position) -> &Hand {
@@ -35,10 +97,10 @@ impl Playing {
     pub fn hand_mut(&mut self, position: Position) -> &mut Hand {
         &mut self.hands[position]
     }
-    pub fn add_litige(&mut self, litige: u64) {
+    pub const fn add_litige(&mut self, litige: u64) {
         self.initial.add_litige(litige);
     }
-    pub fn reset_litige(&mut self) -> u64 {
+    pub const fn reset_litige(&mut self) -> u64 {
         self.initial.reset_litige()
     }
     pub const fn taker(&self) -> Position {
@@ -47,183 +109,509 @@ impl Playing {
     pub const fn hands(&self) -> Hands {
         self.hands
     }
-    pub const fn trump_color(&self) -> Color {
-        self.trump_color
+    pub const fn trump(&self) -> Trump {
+        self.trump
+    }
+    /// Contract won during the coinche auction, `None` in belote.
+    pub const fn taken(&self) -> Option<Taken> {
+        self.taken
+    }
+    /// Trick being played.
+    pub const fn turn(&self) -> &Turn {
+        &self.turn
+    }
+    /// Tricks won so far, in order.
+    pub fn tricks(&self) -> &[Trick] {
+        &self.tricks
+    }
+    pub fn last_trick(&self) -> Option<&Trick> {
+        self.tricks.last()
     }
 }
 
+/// How the contract of the taker ended.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Outcome {
+    /// The taker team won every trick.
+    Capot,
+    /// The taker team got more than half of the points.
+    Made,
+    /// Both teams got the same points, those of the taker team are kept for the next deal.
+    Litige,
+    /// The taker team did not get half of the points, the defense gets everything.
+    Dedans,
+    /// The defense won every trick.
+    DefenseCapot,
+}
+
+impl Outcome {
+    /// Did the taker team fulfill its contract ?
+    pub const fn is_made(&self) -> bool {
+        matches!(self, Self::Capot | Self::Made)
+    }
+}
+
+/// Outcome of a played deal.
+#[allow(clippy::large_enum_variant)]
 pub enum NextGameOrInterrupt {
     NextGame(Game<Initial>),
     Interrupted,
 }
 
 impl Game<Playing> {
+    /// Plays the 8 tricks, then scores the deal into the game points.
     pub fn play(mut self) -> Result<NextGameOrInterrupt, BeloteErrorKind> {
-        let mut belote_rebelote: Option<Team> = None;
-        for position in self.order() {
-            if self.hand(position).belote_rebelote(self.trump_color()) {
-                belote_rebelote = Some(position.team());
+        loop {
+            let decision = self.pending_decision()?;
+            let Some(action) = self.decide(&decision) else {
+                info!("Interrupted.");
+                return Ok(NextGameOrInterrupt::Interrupted);
+            };
+            self = match self.apply(action)? {
+                Step::Pending(game) => game,
+                Step::Over(next_game) => return Ok(NextGameOrInterrupt::NextGame(next_game)),
+            };
+        }
+    }
+
+    /// What the seat to play must decide : its announces before its first card, then its card,
+    /// then whether it announces belote when that card is the first of the trump king and queen.
+    pub fn pending_decision(&self) -> Result<Decision, BeloteErrorKind> {
+        let position = self.current_position;
+        let hand = *self.hand(position);
+        if let Some(card) = self.chosen_card {
+            return Ok(Decision::DeclareBelote {
+                position,
+                hand,
+                card,
+            });
+        }
+        if self.turn.number() == 1 && !self.asked.contains(&position) {
+            let announces = hand.announces();
+            if !announces.is_empty() {
+                return Ok(Decision::Declare {
+                    position,
+                    hand,
+                    announces,
+                });
             }
         }
-        let mut current_position = self.order()[0];
-        let mut attack_points: u64 = 0;
-        let mut defense_points: u64 = 0;
-
-        for turn_number in 0..MAX_CARDS_BY_PLAYER {
-            let mut turn = Turn::new(turn_number as u64 + 1, self.order());
-            loop {
-                info!("{current_position} to play for {turn}");
-                info!(
-                    "Hand of {current_position} before playing : {}",
-                    self.hand(current_position)
-                );
+        let choices = Player::choices(&hand, &position, &self.turn, self.trump(), &self.rules())?;
+        if choices.is_empty() {
+            return Err(BeloteErrorKind::InvalidCase(
+                "no choices available".to_string(),
+            ));
+        }
+        Ok(Decision::PlayCard {
+            position,
+            hand,
+            choices,
+        })
+    }
 
-                let choices = &self.players()[current_position].choices(
-                    self.hand(current_position),
-                    &current_position,
-                    &turn,
-                    self.trump_color(),
-                )?;
-                if choices.is_empty() {
-                    return Err(BeloteErrorKind::InvalidCase(
-                        "no choices available".to_string(),
-                    ));
+    /// Plays the decision of the seat to play, scoring the deal after the last card.
+    pub fn apply(
+        mut self,
+        action: Action,
+    ) -> Result<Step<Playing, Game<Initial>>, BeloteErrorKind> {
+        let decision = self.pending_decision()?;
+        decision.check(&action)?;
+        let position = self.current_position;
+        let card = match action {
+            Action::Declare(announces) => {
+                for announce in announces {
+                    info!("{position} declares {announce}");
+                    self.declared.push((position, announce));
+                    self.notify(GameEvent::AnnounceDeclared { position, announce });
                 }
-
-                turn.called_color().map_or_else(
-                    || info!("{current_position} is first to play, you can choose a color"),
-                    |called_color| info!("{current_position} must play color {called_color}"),
-                );
-
-                let chosen_card = if self.players()[current_position].random() {
-                    let mut rng = rand::thread_rng();
-                    let Some(random_card): Option<Card> = choices.iter().choose(&mut rng).copied()
-                    else {
-                        return Err(BeloteErrorKind::InvalidCase(
-                            "cannot find a random card choice".to_string(),
-                        ));
-                    };
-                    random_card
+                self.asked.push(position);
+                return Ok(Step::Pending(self));
+            }
+            Action::DeclareBelote(declare) => {
+                if declare {
+                    info!("{position} : belote !");
+                    self.belote_declared = true;
+                    self.notify(GameEvent::Belote { position });
                 } else {
-                    loop {
-                        let cards: Vec<String> = choices
-                            .iter()
-                            .map(std::string::ToString::to_string)
-                            .collect();
-                        let page_size = cards.len();
-                        let chosen_card =
-                            Select::new("Which card do you choose ? (ESC to cancel)", cards)
-                                .with_page_size(page_size)
-                                .prompt_skippable();
-                        match chosen_card {
-                            Ok(Some(maybe_chosen_card)) => {
-                                match Card::from_str(&maybe_chosen_card) {
-                                    Ok(chosen_card) => break chosen_card,
-                                    Err(e) => return Err(e),
-                                }
-                            }
-                            Ok(None) => {
-                                info!("Interrupted.");
-                                return Ok(NextGameOrInterrupt::Interrupted);
-                            }
-                            Err(_) => {
-                                info!("Error with questionnaire, try again.");
-                                continue;
-                            }
-                        }
+                    info!("{position} stays silent about its belote");
+                    self.belote_holder = None;
+                }
+                self.chosen_card.take()
+            }
+            Action::PlayCard(card) => {
+                let belote_card = self.trump().color() == Some(card.color())
+                    && matches!(card.value(), Value::King | Value::Queen);
+                if belote_card && self.belote_holder == Some(position) {
+                    if !self.belote_declared {
+                        self.chosen_card = Some(card);
+                        return Ok(Step::Pending(self));
                     }
-                };
-
-                let Some(_) = self.hand_mut(current_position).give(&chosen_card) else {
-                    return Err(BeloteErrorKind::InvalidCase(
-                        "cannot give chosen card".to_string(),
-                    ));
-                };
-                info!(
-                    "Hand of {current_position} after playing : {}",
-                    self.hand(current_position)
-                );
-                turn.put(self.trump_color(), current_position, &chosen_card);
-                if turn.finished() {
-                    break;
+                    info!("{position} : rebelote !");
+                    self.belote_rebelote = Some(position.team());
+                    self.notify(GameEvent::Rebelote { position });
                 }
-                current_position = current_position.next();
+                Some(card)
             }
+            _ => None,
+        };
+        let Some(card) = card else {
+            return Err(BeloteErrorKind::InvalidCase(format!(
+                "{position} has no card to play"
+            )));
+        };
+        self.play_card(position, card)
+    }
 
-            current_position = turn.master_position();
-            info!("Fold master is player {}", turn.master_position());
-            let master_team = turn.master_team();
-            let Some(cards) = turn.take() else {
-                return Err(BeloteErrorKind::InvalidCase(
-                    "Cannot take turn cards".to_string(),
-                ));
-            };
-            for card in cards {
-                let points = card.points(self.trump_color());
-                warn!("{card} : {points} points");
-                if self.taker().team() == master_team {
-                    attack_points += points;
-                } else {
-                    defense_points += points;
-                }
-                self.stack_mut().append_card(card)?;
+    /// Puts `card` on the trick, which is won once every seat played.
+    fn play_card(
+        mut self,
+        position: Position,
+        card: Card,
+    ) -> Result<Step<Playing, Game<Initial>>, BeloteErrorKind> {
+        let Some(_) = self.hand_mut(position).give(&card) else {
+            return Err(BeloteErrorKind::InvalidCase(
+                "cannot give chosen card".to_string(),
+            ));
+        };
+        info!("Hand of {position} after playing : {}", self.hand(position));
+        let trump = self.trump();
+        self.turn.put(trump, position, &card);
+        self.notify(GameEvent::CardPlayed { position, card });
+        if !self.turn.finished() {
+            self.current_position = position.next();
+            return Ok(Step::Pending(self));
+        }
+
+        let number = self.turn.number();
+        let winner = self.turn.master_position();
+        info!("Fold master is player {winner}");
+        let master_team = self.turn.master_team();
+        let Some(trick) = self.turn.take() else {
+            return Err(BeloteErrorKind::InvalidCase(
+                "Cannot take turn cards".to_string(),
+            ));
+        };
+        // The trick is gathered as it was played.
+        let mut trick_points = 0;
+        for (_, card) in *trick.cards() {
+            let points = card.points(trump);
+            warn!("{card} : {points} points");
+            trick_points += points;
+            self.stack_mut().append_card(card)?;
+        }
+        if self.taker().team() == master_team {
+            self.attack_points += trick_points;
+            self.attack_tricks += 1;
+            if winner == self.taker() {
+                self.taker_tricks += 1;
             }
+        } else {
+            self.defense_points += trick_points;
+        }
+        self.current_position = winner;
+        self.tricks.push(trick);
+        self.notify(GameEvent::TrickWon {
+            trick,
+            points: trick_points,
+        });
 
-            if self.stack().len() != (turn_number + 1) * MAX_PLAYERS {
-                return Err(BeloteErrorKind::InvalidCase(format!(
-                    "bad deck length {} it should be {}",
-                    self.stack().len(),
-                    turn_number * MAX_PLAYERS
-                )));
-            };
-            info!("New attack points = {attack_points}");
-            info!("New defense points = {defense_points}");
+        let played = usize::try_from(number).unwrap_or(usize::MAX);
+        if self.stack().len() != played * MAX_PLAYERS {
+            return Err(BeloteErrorKind::InvalidCase(format!(
+                "bad deck length {} it should be {}",
+                self.stack().len(),
+                played * MAX_PLAYERS
+            )));
+        }
+        info!("New attack points = {}", self.attack_points);
+        info!("New defense points = {}", self.defense_points);
+        if played < MAX_CARDS_BY_PLAYER {
+            self.turn = Turn::new(number + 1, Order::starting(winner));
+            return Ok(Step::Pending(self));
         }
+        self.score().map(Step::Over)
+    }
 
-        if current_position.team() == self.taker().team() {
+    /// Scores the deal into the game points, once the 8 tricks are won.
+    fn score(mut self) -> Result<Game<Initial>, BeloteErrorKind> {
+        let (mut attack_points, mut defense_points) = (self.attack_points, self.defense_points);
+        let belote_rebelote = self.belote_rebelote;
+        let taker_team = self.taker().team();
+        if self.current_position.team() == taker_team {
             attack_points += 10;
+        } else {
+            defense_points += 10;
+        }
+        if attack_points + defense_points != 162 {
+            return Err(BeloteErrorKind::InvalidCase(format!(
+                "bad points number : {attack_points} + {defense_points}"
+            )));
         }
 
-        let (contract, total_points) = if belote_rebelote.is_some() {
-            (92, 182)
+        let mut announces = Points::default();
+        if let Some((team, announce_points)) = announce::score(&self.declared, self.trump()) {
+            info!("{team} wins the announces : {announce_points} points");
+            announces[team] = announce_points;
+        }
+
+        let rules = self.rules();
+        let (outcome, final_attack_points, final_defense_points) = match self.taken() {
+            None => {
+                let taker_belote = if belote_rebelote == Some(taker_team) {
+                    20
+                } else {
+                    0
+                };
+                let outcome = Self::belote_outcome(
+                    rules.litige(),
+                    attack_points,
+                    announces[taker_team] + taker_belote,
+                    self.attack_tricks,
+                );
+                let litige = if outcome == Outcome::Litige {
+                    0
+                } else {
+                    self.reset_litige()
+                };
+                let (attack, defense) =
+                    Self::belote_points(outcome, rules, attack_points, defense_points, litige);
+                if outcome == Outcome::Litige && rules.litige() == Litige::Carried {
+                    self.add_litige(attack_points);
+                    self.notify(GameEvent::LitigeCarried {
+                        litige: attack_points,
+                    });
+                }
+                (outcome, attack, defense)
+            }
+            Some(taken) => Self::coinche_score(
+                taken,
+                attack_points,
+                defense_points,
+                announces[taker_team],
+                belote_rebelote,
+                self.attack_tricks,
+                self.taker_tricks,
+            ),
+        };
+
+        let mut points = Self::bonuses(outcome, taker_team, announces, belote_rebelote, rules);
+        points[taker_team] += final_attack_points;
+        points[taker_team.other()] += final_defense_points;
+        let rounding = rules.rounding();
+        for team in Team::iter() {
+            points[team] = rounding.round(points[team]);
+        }
+        for team in Team::iter() {
+            self.add_points(team, points[team]);
+        }
+        self.notify(GameEvent::DealScored {
+            taker: self.taker(),
+            attack_points,
+            defense_points,
+            belote_rebelote,
+            announces,
+            outcome,
+            points,
+        });
+        Ok(self.map(|playing| playing.into().next()))
+    }
+
+    /// Belote : the taker team must get more than half of the points, its own announces and
+    /// belote counting for it, a tie being a litige handled according to the rules.
+    fn belote_outcome(
+        litige: Litige,
+        attack_points: u64,
+        taker_bonus: u64,
+        attack_tricks: usize,
+    ) -> Outcome {
+        let attack = 2 * (attack_points + taker_bonus);
+        let half = 162 + taker_bonus;
+        if attack_tricks == MAX_CARDS_BY_PLAYER {
+            Outcome::Capot
+        } else if attack_tricks == 0 {
+            Outcome::DefenseCapot
+        } else if attack > half {
+            Outcome::Made
+        } else if attack == half && litige != Litige::Dedans {
+            Outcome::Litige
         } else {
-            (81, 162)
+            Outcome::Dedans
+        }
+    }
+
+    /// Card points of the taker team and of the defense, `litige` being the points carried from
+    /// previous deals. A carried litige leaves the taker team nothing for now.
+    fn belote_points(
+        outcome: Outcome,
+        rules: RuleSet,
+        attack_points: u64,
+        defense_points: u64,
+        litige: u64,
+    ) -> (u64, u64) {
+        match outcome {
+            Outcome::Capot => (rules.capot() + litige, 0),
+            Outcome::Made => (attack_points + litige, defense_points),
+            Outcome::Litige if rules.litige() == Litige::Carried => (0, defense_points),
+            Outcome::Litige => (attack_points, defense_points),
+            Outcome::Dedans => (0, 162 + litige),
+            Outcome::DefenseCapot => (0, rules.capot() + litige),
+        }
+    }
+
+    /// Announces and belote : on a failed contract the defense gets those of the taker team,
+    /// its belote excepted when the rules let the taker keep it, and a capot leaves the defense
+    /// nothing.
+    fn bonuses(
+        outcome: Outcome,
+        taker_team: Team,
+        announces: Points,
+        belote_rebelote: Option<Team>,
+        rules: RuleSet,
+    ) -> Points {
+        let defense = taker_team.other();
+        let winner = |team: Team, kept: bool| match outcome {
+            Outcome::Dedans | Outcome::DefenseCapot if !kept => Some(defense),
+            Outcome::Capot if team == defense => None,
+            _ => Some(team),
         };
+        let mut points = Points::default();
+        for team in Team::iter() {
+            if let Some(winner) = winner(team, false) {
+                points[winner] += announces[team];
+            }
+        }
+        if let Some(team) = belote_rebelote {
+            if let Some(winner) = winner(team, rules.belote_on_failed_contract()) {
+                points[winner] += 20;
+            }
+        }
+        points
+    }
 
-        let (final_attack_points, final_defense_points): (u64, u64) = if attack_points == 0 {
-            // capot for defense
-            (0, 252 + self.reset_litige())
-        } else if attack_points >= 1 && attack_points < contract {
-            // dedans
-            (0, 182 + self.reset_litige())
-        } else if attack_points == contract {
-            // litige
-            self.add_litige(contract);
-            (0, contract)
-        } else if attack_points > contract && attack_points < total_points {
-            // reussite
-            (
-                attack_points + self.reset_litige(),
-                total_points - attack_points,
-            )
-        } else if attack_points == total_points {
-            (252 + self.reset_litige(), 0)
+    /// Coinche : the taker team must get more than half of the card points and at least its
+    /// contract, its announces and belote counting for the contract. A capot must win every trick, a generale
+    /// every trick by the taker alone.
+    ///
+    /// A contract made scores its value plus the points made, the defense keeping its own points.
+    /// Coinched or surcoinched, the winning team scores 162 plus the doubled contract, the other
+    /// team nothing. A contract failed gives 162 plus the contract to the defense.
+    fn coinche_score(
+        taken: Taken,
+        attack_points: u64,
+        defense_points: u64,
+        taker_announces: u64,
+        belote_rebelote: Option<Team>,
+        attack_tricks: usize,
+        taker_tricks: usize,
+    ) -> (Outcome, u64, u64) {
+        let attack_belote = if belote_rebelote == Some(taken.taker().team()) {
+            20
         } else {
-            return Err(BeloteErrorKind::InvalidCase(format!(
-                "bad points number : {attack_points}"
-            )));
+            0
         };
+        let made = match taken.level() {
+            Level::Capot => attack_tricks == MAX_CARDS_BY_PLAYER,
+            Level::Generale => taker_tricks == MAX_CARDS_BY_PLAYER,
+            level => {
+                attack_points > 81
+                    && attack_points + taker_announces + attack_belote >= level.value()
+            }
+        };
+        let contract_points = taken.level().value() * taken.multiplier();
+        if made {
+            let outcome = if attack_tricks == MAX_CARDS_BY_PLAYER {
+                Outcome::Capot
+            } else {
+                Outcome::Made
+            };
+            if taken.interruption().is_some() {
+                (outcome, 162 + contract_points, 0)
+            } else {
+                (outcome, contract_points + attack_points, defense_points)
+            }
+        } else {
+            let outcome = if attack_tricks == 0 {
+                Outcome::DefenseCapot
+            } else {
+                Outcome::Dedans
+            };
+            (outcome, 0, 162 + contract_points)
+        }
+    }
+}
+
+#[test]
+fn playing_tests() {
+    let rules = RuleSet::default();
+    let (taker, defense) = (Team::NorthSouth, Team::WestEast);
+    let outcome = |attack_points, taker_bonus, attack_tricks| {
+        Game::<Playing>::belote_outcome(rules.litige(), attack_points, taker_bonus, attack_tricks)
+    };
+    let bonuses = |outcome, announces, belote_rebelote, rules| {
+        let points = Game::<Playing>::bonuses(outcome, taker, announces, belote_rebelote, rules);
+        (points[taker], points[defense])
+    };
+    let none = Points::default();
+    let mut tierce = Points::default();
+    tierce[taker] = 20;
+
+    // A tierce makes a contract the card points alone would fail.
+    assert_eq!(outcome(75, 20, 4), Outcome::Made);
+    assert_eq!(outcome(75, 0, 4), Outcome::Dedans);
+    assert_eq!(bonuses(Outcome::Made, tierce, None, rules), (20, 0));
+
+    // Dedans despite the tierce : the defense gets the announce too.
+    assert_eq!(outcome(70, 20, 3), Outcome::Dedans);
+    assert_eq!(bonuses(Outcome::Dedans, tierce, None, rules), (0, 20));
 
-        if let Some(belote_rebelote_team) = belote_rebelote {
-            self.add_points(belote_rebelote_team, 20);
-        }
-        self.add_points(self.taker().team(), final_attack_points);
-        self.add_points(self.taker().team().other(), final_defense_points);
-        let players = self.players();
-        let points = self.points();
-        let initial = self.into().into().next();
-        Ok(NextGameOrInterrupt::NextGame(Game::new(
-            players, points, initial,
-        )))
+    // The taker's belote counts toward its contract, the defense's does not.
+    assert_eq!(outcome(72, 20, 5), Outcome::Made);
+    assert_eq!(outcome(70, 20, 5), Outcome::Dedans);
+    assert_eq!(outcome(82, 0, 5), Outcome::Made);
+    assert_eq!(bonuses(Outcome::Made, none, Some(taker), rules), (20, 0));
+    assert_eq!(bonuses(Outcome::Made, none, Some(defense), rules), (0, 20));
+
+    // A failed taker keeps its belote only if the rules say so.
+    let mut strict = rules;
+    strict.set_belote_on_failed_contract(false);
+    assert_eq!(bonuses(Outcome::Dedans, none, Some(taker), rules), (20, 0));
+    assert_eq!(bonuses(Outcome::Dedans, none, Some(taker), strict), (0, 20));
+
+    // A capot leaves the defense nothing, its belote included.
+    assert_eq!(outcome(162, 0, MAX_CARDS_BY_PLAYER), Outcome::Capot);
+    assert_eq!(bonuses(Outcome::Capot, none, Some(defense), rules), (0, 0));
+    assert_eq!(bonuses(Outcome::Capot, tierce, Some(taker), rules), (40, 0));
+
+    // Capot and litige under every rule set, the taker's belote making the tie.
+    for litige in Litige::iter() {
+        for capot in [250, 252, 500] {
+            let mut rules = rules;
+            rules.set_litige(litige);
+            rules.set_capot(capot);
+            let points = |outcome, attack_points| {
+                Game::<Playing>::belote_points(
+                    outcome,
+                    rules,
+                    attack_points,
+                    162 - attack_points,
+                    30,
+                )
+            };
+            let tie = Game::<Playing>::belote_outcome(litige, 71, 20, 4);
+            match litige {
+                Litige::Carried => assert_eq!(points(tie, 71), (0, 91)),
+                Litige::Split => assert_eq!(points(tie, 71), (71, 91)),
+                Litige::Dedans => assert_eq!(points(tie, 71), (0, 192)),
+            }
+            assert_eq!(tie == Outcome::Litige, litige != Litige::Dedans);
+            assert_eq!(bonuses(tie, none, Some(taker), rules), (20, 0));
+            let capot_outcome =
+                Game::<Playing>::belote_outcome(litige, 162, 20, MAX_CARDS_BY_PLAYER);
+            assert_eq!(capot_outcome, Outcome::Capot);
+            assert_eq!(points(capot_outcome, 162), (capot + 30, 0));
+            let defense_capot = Game::<Playing>::belote_outcome(litige, 0, 20, 0);
+            assert_eq!(defense_capot, Outcome::DefenseCapot);
+            assert_eq!(points(defense_capot, 0), (0, capot + 30));
+        }
     }
 }
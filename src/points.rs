@@ -1,7 +1,9 @@
 use crate::team::Team;
+use serde::{Deserialize, Serialize};
 use std::ops::{Index, IndexMut};
 
-#[derive(Debug, Default, Copy, Clone)]
+/// Cumulated points of both teams.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Points {
     north_south: u64,
     west_east: u64,
@@ -1,10 +1,25 @@
-use strum_macros::{EnumCount, EnumIter, VariantArray};
+use strum_macros::{EnumCount, EnumIter, EnumString, VariantArray};
 
 use crate::team::Team;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 // KEEP THIS ORDER
-#[derive(Eq, PartialEq, Clone, Copy, Debug, VariantArray, EnumIter, EnumCount)]
+#[derive(
+    Eq,
+    PartialEq,
+    Clone,
+    Copy,
+    Debug,
+    Hash,
+    VariantArray,
+    EnumIter,
+    EnumCount,
+    EnumString,
+    Serialize,
+    Deserialize,
+)]
+#[strum(ascii_case_insensitive)]
 pub enum Position {
     North,
     East,
@@ -31,6 +46,11 @@ impl Position {
         }
     }
 
+    #[must_use]
+    pub const fn partner(&self) -> Self {
+        self.next().next()
+    }
+
     #[must_use]
     pub const fn next(&self) -> Self {
         match self {
@@ -0,0 +1,382 @@
+use crate::agent::Agent;
+use crate::announce::Announce;
+use crate::bidding::{Bid, PlayOrNext};
+use crate::card::{Card, Trump};
+use crate::coinche::Bids;
+use crate::errors::BeloteErrorKind;
+use crate::event::{GameEvent, Observer};
+use crate::game::Game;
+use crate::initial::Initial;
+use crate::player::Player;
+use crate::players::Players;
+use crate::playing::NextGameOrInterrupt;
+use crate::points::Points;
+use crate::position::Position;
+use crate::team::Team;
+use crate::variant::Variant;
+use crate::view::PlayerView;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use strum::IntoEnumIterator;
+use tracing::info;
+
+/// Events of a single deal, from its `DealStarted` to its `DealScored`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DealRecord {
+    events: Vec<GameEvent>,
+}
+
+impl DealRecord {
+    pub fn events(&self) -> &[GameEvent] {
+        &self.events
+    }
+
+    /// Points won by each team during this deal, nothing when the cards were redistributed.
+    pub fn points(&self) -> Points {
+        self.events
+            .iter()
+            .find_map(|event| match event {
+                GameEvent::DealScored { points, .. } => Some(*points),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// Plays the deal again, checking that every recorded move is still accepted by the engine
+    /// and that the very same events are produced.
+    pub fn replay(&self) -> Result<Points, BeloteErrorKind> {
+        let Some(GameEvent::DealStarted {
+            number,
+            order,
+            stack,
+            litige,
+            variant,
+            rules,
+        }) = self.events.first().copied()
+        else {
+            return Err(BeloteErrorKind::InvalidRecord(
+                "deal must start with DealStarted".to_string(),
+            ));
+        };
+
+        let seat = |position: Position| Player::from(Replayer::new(position, &self.events));
+        let players = Players::new(
+            seat(Position::North),
+            seat(Position::South),
+            seat(Position::East),
+            seat(Position::West),
+        );
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut game = Game::new(
+            players,
+            Points::default(),
+            StdRng::seed_from_u64(0),
+            rules,
+            Vec::new(),
+            Initial::restore(order, number, stack, litige, variant),
+        );
+        game.observe(Arc::clone(&events));
+
+        let distribution = game.deal()?;
+        let play_or_next = match variant {
+            Variant::Belote => distribution.bidding()?.playing_game_or_redistribute()?,
+            Variant::Coinche => distribution.auction()?.playing_game_or_redistribute()?,
+        };
+        match play_or_next {
+            PlayOrNext::NextGame(_) => {}
+            PlayOrNext::PlayGame(playing) => match playing.play()? {
+                NextGameOrInterrupt::NextGame(_) => {}
+                NextGameOrInterrupt::Interrupted => {
+                    return Err(BeloteErrorKind::InvalidRecord(format!(
+                        "deal {number} is missing cards"
+                    )))
+                }
+            },
+            PlayOrNext::Interrupted => {
+                return Err(BeloteErrorKind::InvalidRecord(format!(
+                    "deal {number} is missing bids"
+                )))
+            }
+        }
+
+        let Ok(events) = events.lock() else {
+            return Err(BeloteErrorKind::InvalidRecord(
+                "cannot read replayed events".to_string(),
+            ));
+        };
+        if let Some((index, (recorded, replayed))) = self
+            .events
+            .iter()
+            .zip(events.iter())
+            .enumerate()
+            .find(|(_, (recorded, replayed))| recorded != replayed)
+        {
+            return Err(BeloteErrorKind::InvalidRecord(format!(
+                "deal {number}, event {index} : recorded {recorded:?} but replayed {replayed:?}"
+            )));
+        }
+        if self.events.len() != events.len() {
+            return Err(BeloteErrorKind::InvalidRecord(format!(
+                "deal {number} : {} events recorded but {} replayed",
+                self.events.len(),
+                events.len()
+            )));
+        }
+        Ok(self.points())
+    }
+}
+
+/// A whole game : who was seated where, every deal and the final points.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    /// Agent of North, East, South and West.
+    seating: [String; 4],
+    deals: Vec<DealRecord>,
+    points: Option<Points>,
+}
+
+impl GameRecord {
+    pub fn new(players: &Players) -> Self {
+        Self {
+            seating: [
+                players[Position::North].to_string(),
+                players[Position::East].to_string(),
+                players[Position::South].to_string(),
+                players[Position::West].to_string(),
+            ],
+            deals: Vec::new(),
+            points: None,
+        }
+    }
+
+    pub const fn seating(&self) -> &[String; 4] {
+        &self.seating
+    }
+
+    pub fn deals(&self) -> &[DealRecord] {
+        &self.deals
+    }
+
+    /// Final points, known once the game ended.
+    pub const fn points(&self) -> Option<Points> {
+        self.points
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), BeloteErrorKind> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| BeloteErrorKind::InvalidRecord(e.to_string()))?;
+        fs::write(path, json).map_err(|e| BeloteErrorKind::InvalidRecord(e.to_string()))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, BeloteErrorKind> {
+        let json =
+            fs::read_to_string(path).map_err(|e| BeloteErrorKind::InvalidRecord(e.to_string()))?;
+        serde_json::from_str(&json).map_err(|e| BeloteErrorKind::InvalidRecord(e.to_string()))
+    }
+
+    /// Replays every deal, then checks the cumulated points against the recorded ones.
+    pub fn replay(&self) -> Result<Points, BeloteErrorKind> {
+        let mut points = Points::default();
+        for deal in &self.deals {
+            let deal_points = deal.replay()?;
+            for team in Team::iter() {
+                points[team] += deal_points[team];
+            }
+            info!(
+                "Deal replayed, {} = {}, {} = {}",
+                Team::NorthSouth,
+                points[Team::NorthSouth],
+                Team::WestEast,
+                points[Team::WestEast]
+            );
+        }
+        if let Some(recorded) = self.points {
+            if recorded != points {
+                return Err(BeloteErrorKind::InvalidRecord(format!(
+                    "recorded points {recorded:?} but replayed {points:?}"
+                )));
+            }
+        }
+        Ok(points)
+    }
+}
+
+/// Observer recording the game, shared so it can be saved once the game is over.
+#[derive(Clone, Debug, Default)]
+pub struct Recorder(Arc<Mutex<GameRecord>>);
+
+impl Recorder {
+    pub fn new(players: &Players) -> Self {
+        Self(Arc::new(Mutex::new(GameRecord::new(players))))
+    }
+
+    /// What has been recorded so far.
+    pub fn record(&self) -> GameRecord {
+        self.0
+            .lock()
+            .map(|record| record.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl Observer for Recorder {
+    fn notify(&mut self, event: &GameEvent) {
+        let Ok(mut record) = self.0.lock() else {
+            return;
+        };
+        match event {
+            GameEvent::MatchEnded { points, .. } => {
+                record.points = Some(*points);
+            }
+            GameEvent::DealStarted { .. } => {
+                record.deals.push(DealRecord {
+                    events: vec![*event],
+                });
+            }
+            _ => {
+                if let Some(deal) = record.deals.last_mut() {
+                    deal.events.push(*event);
+                }
+            }
+        }
+    }
+}
+
+/// Agent giving back the recorded decisions of a seat, in order.
+#[derive(Debug)]
+struct Replayer {
+    bids: VecDeque<Bid>,
+    announces: Vec<Announce>,
+    belote: bool,
+    cards: VecDeque<Card>,
+}
+
+impl Replayer {
+    fn new(position: Position, events: &[GameEvent]) -> Self {
+        let mut bids = VecDeque::new();
+        let mut announces = Vec::new();
+        let mut belote = false;
+        let mut cards = VecDeque::new();
+        for event in events {
+            match *event {
+                GameEvent::BidMade { position: p, bid } if p == position => bids.push_back(bid),
+                GameEvent::AnnounceDeclared {
+                    position: p,
+                    announce,
+                } if p == position => announces.push(announce),
+                GameEvent::Belote { position: p } if p == position => belote = true,
+                GameEvent::CardPlayed { position: p, card } if p == position => {
+                    cards.push_back(card);
+                }
+                _ => {}
+            }
+        }
+        Self {
+            bids,
+            announces,
+            belote,
+            cards,
+        }
+    }
+}
+
+impl Agent for Replayer {
+    fn take(&mut self, _: &PlayerView, _: Card, _: &mut dyn RngCore) -> Option<bool> {
+        match self.bids.pop_front()? {
+            Bid::Take => Some(true),
+            Bid::Pass => Some(false),
+            _ => None,
+        }
+    }
+
+    fn choose_color(
+        &mut self,
+        _: &PlayerView,
+        _: Card,
+        _: &mut dyn RngCore,
+    ) -> Option<Option<Trump>> {
+        match self.bids.pop_front()? {
+            Bid::Color(trump) => Some(Some(trump)),
+            Bid::Pass => Some(None),
+            _ => None,
+        }
+    }
+
+    fn bid(&mut self, _: &PlayerView, _: &Bids, _: &mut dyn RngCore) -> Option<Bid> {
+        self.bids.pop_front()
+    }
+
+    fn declare(
+        &mut self,
+        _: &PlayerView,
+        _: &[Announce],
+        _: &mut dyn RngCore,
+    ) -> Option<Vec<Announce>> {
+        Some(std::mem::take(&mut self.announces))
+    }
+
+    fn declare_belote(&mut self, _: &PlayerView, _: Card, _: &mut dyn RngCore) -> Option<bool> {
+        Some(self.belote)
+    }
+
+    fn choose_card(&mut self, _: &PlayerView, _: &[Card], _: &mut dyn RngCore) -> Option<Card> {
+        self.cards.pop_front()
+    }
+}
+
+#[test]
+fn record_tests() {
+    use crate::agent::Random;
+    use crate::order::Order;
+
+    let random = Random::for_table(4);
+    let players = Players::new(
+        Player::from(random),
+        Player::from(random),
+        Player::from(random),
+        Player::from(random),
+    );
+    let recorder = Recorder::new(&players);
+    let mut game = Game::default(players, Order::default(), StdRng::seed_from_u64(3));
+    game.observe(recorder.clone());
+    for _ in 0..10 {
+        let Ok(bidding) = game.distribute().and_then(Game::bidding) else {
+            panic!("cannot deal");
+        };
+        game = match bidding.playing_game_or_redistribute() {
+            Ok(PlayOrNext::NextGame(next_game)) => next_game,
+            Ok(PlayOrNext::PlayGame(playing)) => match playing.play() {
+                Ok(NextGameOrInterrupt::NextGame(next_game)) => next_game,
+                _ => panic!("deal should be played"),
+            },
+            _ => panic!("bidding should end"),
+        };
+    }
+    let points = game.end(None);
+
+    let record = recorder.record();
+    assert_eq!(record.deals().len(), 10);
+    assert_eq!(record.points(), Some(points));
+    assert_eq!(record.replay(), Ok(points));
+
+    let json = serde_json::to_string(&record).unwrap_or_default();
+    let loaded: GameRecord = serde_json::from_str(&json).unwrap_or_default();
+    assert_eq!(loaded, record);
+
+    let mut cheated = record;
+    for deal in &mut cheated.deals {
+        let played = deal
+            .events
+            .iter()
+            .rposition(|event| matches!(event, GameEvent::CardPlayed { .. }));
+        if let Some(index) = played {
+            deal.events.swap(index, index - 1);
+        }
+    }
+    assert!(cheated.replay().is_err());
+}
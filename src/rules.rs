@@ -0,0 +1,181 @@
+use crate::errors::BeloteErrorKind;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// How the 5 first cards are dealt to each player.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    EnumIter,
+    EnumString,
+    Display,
+    Serialize,
+    Deserialize,
+)]
+pub enum Dealing {
+    #[default]
+    #[strum(serialize = "3-2")]
+    ThreeTwo,
+    #[strum(serialize = "2-3")]
+    TwoThree,
+}
+
+impl Dealing {
+    /// Cards given to each player at the first then at the second round.
+    pub const fn rounds(self) -> [usize; 2] {
+        match self {
+            Self::ThreeTwo => [3, 2],
+            Self::TwoThree => [2, 3],
+        }
+    }
+}
+
+/// What happens when the taker team gets exactly half of the points.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    EnumIter,
+    EnumString,
+    Display,
+    Serialize,
+    Deserialize,
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum Litige {
+    /// The defense scores its half, the half of the taker team goes to the winner of the next deal.
+    #[default]
+    Carried,
+    /// Both teams score their half.
+    Split,
+    /// The taker team is dedans.
+    Dedans,
+}
+
+/// How deal points are written down.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    EnumIter,
+    EnumString,
+    Display,
+    Serialize,
+    Deserialize,
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum Rounding {
+    #[default]
+    Exact,
+    /// To the nearest ten, a 5 being rounded down : 85 gives 80, 86 gives 90.
+    Tens,
+}
+
+impl Rounding {
+    pub const fn round(self, points: u64) -> u64 {
+        match self {
+            Self::Exact => points,
+            Self::Tens => (points + 4) / 10 * 10,
+        }
+    }
+}
+
+/// House rules of a table, the default being the most common ones.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleSet {
+    /// Must a player without the called color cut and overtrump even when its partner is master ?
+    overtrump_on_partner: bool,
+    dealing: Dealing,
+    litige: Litige,
+    /// Points of the taker team winning every trick, or of the defense doing so.
+    capot: u64,
+    /// Does the holder of belote still score it when its team is dedans ?
+    belote_on_failed_contract: bool,
+    rounding: Rounding,
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self {
+            overtrump_on_partner: false,
+            dealing: Dealing::default(),
+            litige: Litige::default(),
+            capot: 252,
+            belote_on_failed_contract: true,
+            rounding: Rounding::default(),
+        }
+    }
+}
+
+impl RuleSet {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, BeloteErrorKind> {
+        let json =
+            fs::read_to_string(path).map_err(|e| BeloteErrorKind::InvalidRules(e.to_string()))?;
+        serde_json::from_str(&json).map_err(|e| BeloteErrorKind::InvalidRules(e.to_string()))
+    }
+
+    pub const fn overtrump_on_partner(&self) -> bool {
+        self.overtrump_on_partner
+    }
+    pub const fn dealing(&self) -> Dealing {
+        self.dealing
+    }
+    pub const fn litige(&self) -> Litige {
+        self.litige
+    }
+    pub const fn capot(&self) -> u64 {
+        self.capot
+    }
+    pub const fn belote_on_failed_contract(&self) -> bool {
+        self.belote_on_failed_contract
+    }
+    pub const fn rounding(&self) -> Rounding {
+        self.rounding
+    }
+
+    pub const fn set_overtrump_on_partner(&mut self, overtrump_on_partner: bool) {
+        self.overtrump_on_partner = overtrump_on_partner;
+    }
+    pub const fn set_dealing(&mut self, dealing: Dealing) {
+        self.dealing = dealing;
+    }
+    pub const fn set_litige(&mut self, litige: Litige) {
+        self.litige = litige;
+    }
+    pub const fn set_capot(&mut self, capot: u64) {
+        self.capot = capot;
+    }
+    pub const fn set_belote_on_failed_contract(&mut self, belote_on_failed_contract: bool) {
+        self.belote_on_failed_contract = belote_on_failed_contract;
+    }
+    pub const fn set_rounding(&mut self, rounding: Rounding) {
+        self.rounding = rounding;
+    }
+}
+
+#[test]
+fn rules_tests() {
+    let rules: RuleSet =
+        serde_json::from_str(r#"{"dealing": "TwoThree", "capot": 250}"#).unwrap_or_default();
+    assert_eq!(rules.dealing().rounds(), [2, 3]);
+    assert_eq!(rules.capot(), 250);
+    assert_eq!(rules.litige(), Litige::Carried);
+    assert!(rules.belote_on_failed_contract());
+
+    assert_eq!(Rounding::Tens.round(85), 80);
+    assert_eq!(Rounding::Tens.round(86), 90);
+    assert_eq!(Rounding::Exact.round(86), 86);
+}
@@ -0,0 +1,252 @@
+use crate::errors::BeloteErrorKind;
+use crate::helpers;
+use crate::rules::RuleSet;
+use crate::statistics::Statistics;
+use crate::variant::Variant;
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use std::time::Instant;
+
+/// Seed of the game `game` of a simulation seeded by `master`, scrambled by splitmix64 so that
+/// neighbouring games do not get neighbouring seeds.
+pub const fn game_seed(master: u64, game: u64) -> u64 {
+    let mut seed = master.wrapping_add(game.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    seed = (seed ^ (seed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    seed = (seed ^ (seed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    seed ^ (seed >> 31)
+}
+
+/// Game of a simulation which failed, to be played again alone from its seed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Failure {
+    game: u64,
+    seed: u64,
+    error: BeloteErrorKind,
+}
+
+impl Failure {
+    pub const fn game(&self) -> u64 {
+        self.game
+    }
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+    pub const fn error(&self) -> &BeloteErrorKind {
+        &self.error
+    }
+}
+
+/// Statistics of the games played and the games which failed, in order.
+#[derive(Debug, Default)]
+pub struct Simulation {
+    statistics: Statistics,
+    failures: Vec<Failure>,
+}
+
+impl Simulation {
+    pub const fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+    pub fn failures(&self) -> &[Failure] {
+        &self.failures
+    }
+}
+
+/// Plays a number of single deal games between random players, shared among worker threads.
+///
+/// Every game is seeded by [`game_seed`] from its number, and each worker plays a contiguous
+/// range of them, so that a simulation gives the same results whatever its concurrency.
+#[derive(Debug, Clone, Copy)]
+pub struct Simulator {
+    games: u64,
+    concurrency: NonZeroUsize,
+    seed: u64,
+    variant: Variant,
+    rules: RuleSet,
+}
+
+impl Simulator {
+    pub fn new(games: u64, concurrency: NonZeroUsize) -> Self {
+        Self {
+            games,
+            concurrency,
+            seed: 0,
+            variant: Variant::default(),
+            rules: RuleSet::default(),
+        }
+    }
+
+    pub const fn games(&self) -> u64 {
+        self.games
+    }
+    pub const fn concurrency(&self) -> NonZeroUsize {
+        self.concurrency
+    }
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+    pub const fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+    pub const fn variant(&self) -> Variant {
+        self.variant
+    }
+    pub const fn set_variant(&mut self, variant: Variant) {
+        self.variant = variant;
+    }
+    pub const fn rules(&self) -> RuleSet {
+        self.rules
+    }
+    pub const fn set_rules(&mut self, rules: RuleSet) {
+        self.rules = rules;
+    }
+
+    /// Plays the game seeded by `seed` alone.
+    pub fn replay(&self, seed: u64) -> Result<Statistics, BeloteErrorKind> {
+        helpers::test_game(1, seed, self.variant, self.rules)
+    }
+
+    /// Plays the games `range` in order with `play`, a game which panics failing alone.
+    fn work(
+        &self,
+        range: std::ops::Range<u64>,
+        play: impl Fn(u64) -> Result<Statistics, BeloteErrorKind>,
+    ) -> Simulation {
+        let mut simulation = Simulation::default();
+        for game in range {
+            let seed = game_seed(self.seed, game);
+            let played =
+                panic::catch_unwind(AssertUnwindSafe(|| play(seed))).unwrap_or_else(|payload| {
+                    let message = payload
+                        .downcast_ref::<&str>()
+                        .map(ToString::to_string)
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_default();
+                    Err(BeloteErrorKind::InvalidCase(format!(
+                        "the game panicked : {message}"
+                    )))
+                });
+            match played {
+                Ok(statistics) => simulation.statistics.merge(&statistics),
+                Err(error) => simulation.failures.push(Failure { game, seed, error }),
+            }
+        }
+        simulation
+    }
+
+    /// Games `range` all recorded as failed, their worker having panicked before telling which.
+    fn lost(&self, range: std::ops::Range<u64>) -> Simulation {
+        let failures = range
+            .map(|game| Failure {
+                game,
+                seed: game_seed(self.seed, game),
+                error: BeloteErrorKind::InvalidCase(
+                    "the worker playing this game panicked".to_string(),
+                ),
+            })
+            .collect();
+        Simulation {
+            statistics: Statistics::default(),
+            failures,
+        }
+    }
+
+    /// Plays every game, merging the results of the workers in the order of their games.
+    pub fn run(&self) -> Simulation {
+        let start = Instant::now();
+        let workers = u64::try_from(self.concurrency.get()).unwrap_or(1);
+        let share = self.games.div_ceil(workers);
+        let simulations: Vec<Simulation> = thread::scope(|scope| {
+            // Every worker is spawned before the first one is joined.
+            #[allow(clippy::needless_collect)]
+            let handles: Vec<_> = (0..workers)
+                .map(|worker| {
+                    let start = (worker * share).min(self.games);
+                    let end = (start + share).min(self.games);
+                    (
+                        start..end,
+                        scope.spawn(move || self.work(start..end, |seed| self.replay(seed))),
+                    )
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|(range, handle)| handle.join().unwrap_or_else(|_| self.lost(range)))
+                .collect()
+        });
+        let mut simulation = Simulation::default();
+        for worker in simulations {
+            simulation.statistics.merge(&worker.statistics);
+            simulation.failures.extend(worker.failures);
+        }
+        simulation
+            .statistics
+            .set_seconds(start.elapsed().as_secs_f64());
+        simulation
+    }
+}
+
+#[test]
+fn simulator_tests() {
+    use crate::team::Team;
+
+    assert_ne!(game_seed(0, 0), game_seed(0, 1));
+    assert_ne!(game_seed(0, 1), game_seed(1, 0));
+
+    let games = 101;
+    let mut single = Simulator::new(games, NonZeroUsize::MIN);
+    single.set_seed(5);
+    let mut parallel = single;
+    parallel.concurrency = NonZeroUsize::new(3).unwrap_or(NonZeroUsize::MIN);
+    let (single, parallel) = (single.run(), parallel.run());
+    assert!(single.failures().is_empty());
+    for simulation in [&single, &parallel] {
+        let failed = simulation.failures().len() as u64;
+        assert_eq!(simulation.statistics().deals() + failed, games);
+    }
+    assert_eq!(single.statistics().deals(), games);
+    assert_eq!(parallel.statistics().deals(), games);
+    assert_eq!(
+        single.statistics().outcomes(),
+        parallel.statistics().outcomes()
+    );
+    assert_eq!(single.statistics().points(), parallel.statistics().points());
+
+    let Ok(alone) = Simulator::new(1, NonZeroUsize::MIN).replay(game_seed(5, 0)) else {
+        panic!("a game should be played again from its seed");
+    };
+    let mut first = Simulator::new(1, NonZeroUsize::MIN);
+    first.set_seed(5);
+    assert_eq!(
+        alone.points()[Team::NorthSouth],
+        first.run().statistics().points()[Team::NorthSouth]
+    );
+
+    // A game which panics fails alone, the others of its worker being played.
+    let panicking = game_seed(5, 2);
+    let worked = first.work(0..4, |seed| {
+        assert_ne!(seed, panicking, "this game panics");
+        first.replay(seed)
+    });
+    assert_eq!(worked.statistics().deals(), 3);
+    assert_eq!(
+        worked
+            .failures()
+            .iter()
+            .map(Failure::game)
+            .collect::<Vec<_>>(),
+        vec![2]
+    );
+
+    // The games of a worker which panicked are all failures, to be played again by seed.
+    let lost = first.lost(3..5);
+    assert_eq!(lost.statistics().deals(), 0);
+    assert_eq!(
+        lost.failures()
+            .iter()
+            .map(Failure::seed)
+            .collect::<Vec<_>>(),
+        vec![game_seed(5, 3), game_seed(5, 4)]
+    );
+}
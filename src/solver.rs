@@ -0,0 +1,466 @@
+use crate::card::{Card, Trump};
+use crate::card_set::CardSet;
+use crate::constants::MAX_CARDS_BY_PLAYER;
+use crate::errors::BeloteErrorKind;
+use crate::hands::Hands;
+use crate::order::Order;
+use crate::player::Player;
+use crate::points::Points;
+use crate::position::Position;
+use crate::rules::RuleSet;
+use crate::team::Team;
+use crate::turn::Turn;
+use std::collections::HashMap;
+use strum::IntoEnumIterator;
+
+/// A deal with every card face up, at any point of its play.
+#[derive(Clone, Copy, Debug)]
+pub struct Layout {
+    hands: Hands,
+    turn: Turn,
+    next: Position,
+    trump: Trump,
+    rules: RuleSet,
+}
+
+impl Layout {
+    /// Layout where `trick` has already been played from `leader`, `hands` holding the cards left.
+    ///
+    /// Fails when the leader holds too many cards for the trick, when the trick is not played in
+    /// seat order from the leader, or when one of its cards is still in a hand.
+    pub fn new(
+        hands: Hands,
+        trick: &[(Position, Card)],
+        leader: Position,
+        trump: Trump,
+        rules: RuleSet,
+    ) -> Result<Self, BeloteErrorKind> {
+        let before_trick = hands[leader].len() + usize::from(!trick.is_empty());
+        let Some(played) = MAX_CARDS_BY_PLAYER.checked_sub(before_trick) else {
+            return Err(BeloteErrorKind::InvalidCase(format!(
+                "{leader} holds {} cards, too many to have led a trick",
+                hands[leader].len()
+            )));
+        };
+        let order = Order::starting(leader);
+        let mut turn = Turn::new(played as u64 + 1, order);
+        for (index, (position, card)) in trick.iter().enumerate() {
+            if order.into_iter().nth(index) != Some(*position) {
+                return Err(BeloteErrorKind::InvalidCase(format!(
+                    "{position} plays out of turn in a trick led by {leader}"
+                )));
+            }
+            if Position::iter().any(|holder| hands[holder].contains(card))
+                || !turn.put(trump, *position, card)
+            {
+                return Err(BeloteErrorKind::InvalidCard(card.to_string()));
+            }
+        }
+        Ok(Self::from_turn(hands, turn, trump, rules))
+    }
+
+    /// Layout where `turn` is being played, `hands` holding the cards left.
+    pub fn from_turn(hands: Hands, turn: Turn, trump: Trump, rules: RuleSet) -> Self {
+        let order = turn.order();
+        let next = order
+            .into_iter()
+            .find(|position| turn.card(*position).is_none())
+            .unwrap_or_else(|| order.first());
+        Self {
+            hands,
+            turn,
+            next,
+            trump,
+            rules,
+        }
+    }
+
+    pub const fn hands(&self) -> Hands {
+        self.hands
+    }
+    /// Player to play.
+    pub const fn next(&self) -> Position {
+        self.next
+    }
+    pub const fn trump(&self) -> Trump {
+        self.trump
+    }
+
+    /// Legal cards of the player to play.
+    pub fn choices(&self) -> Result<CardSet, BeloteErrorKind> {
+        Player::choices(
+            &self.hands[self.next],
+            &self.next,
+            &self.turn,
+            self.trump,
+            &self.rules,
+        )
+        .map(|choices| choices.legal())
+    }
+
+    /// Plays `card`, giving back the winner and the points of the trick when it is over.
+    pub fn play(&self, card: Card) -> Result<(Self, Option<(Position, u64)>), BeloteErrorKind> {
+        let mut layout = *self;
+        let position = self.next;
+        if layout.hands[position].give(&card).is_none() {
+            return Err(BeloteErrorKind::InvalidCard(card.to_string()));
+        }
+        layout.turn.put(self.trump, position, &card);
+        if !layout.turn.finished() {
+            layout.next = position.next();
+            return Ok((layout, None));
+        }
+        let winner = layout.turn.master_position();
+        let number = layout.turn.number();
+        let Some(trick) = layout.turn.take() else {
+            return Err(BeloteErrorKind::InvalidCase("unfinished trick".to_string()));
+        };
+        let mut points = trick.points(self.trump);
+        if layout.is_over() {
+            points += 10;
+        }
+        layout.turn = Turn::new(number + 1, Order::starting(winner));
+        layout.next = winner;
+        Ok((layout, Some((winner, points))))
+    }
+
+    /// Checks that no card is given twice and that every player holds as many cards as it should.
+    pub fn check(&self) -> Result<(), BeloteErrorKind> {
+        let mut seen = CardSet::EMPTY;
+        for position in Position::iter() {
+            let table = self.turn.card(position);
+            for card in self.hands[position].into_iter().chain(table) {
+                if !seen.insert(card) {
+                    return Err(BeloteErrorKind::InvalidCard(card.to_string()));
+                }
+            }
+        }
+        let leader = self.turn.order().first();
+        let tricks = self.hands[leader].len() + usize::from(self.turn.card(leader).is_some());
+        for position in Position::iter() {
+            let expected = tricks - usize::from(self.turn.card(position).is_some());
+            if self.hands[position].len() != expected {
+                return Err(BeloteErrorKind::InvalidCase(format!(
+                    "{position} should hold {expected} cards"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_over(&self) -> bool {
+        Position::iter().all(|position| self.hands[position].is_empty())
+    }
+
+    /// Points still to be won, dix de der included.
+    fn remaining_points(&self) -> u64 {
+        let in_hands: u64 = Position::iter()
+            .flat_map(|position| self.hands[position].into_iter())
+            .map(|card| card.points(self.trump))
+            .sum();
+        let on_table: u64 = Position::iter()
+            .filter_map(|position| self.turn.card(position))
+            .map(|card| card.points(self.trump))
+            .sum();
+        in_hands + on_table + 10
+    }
+
+    /// Cards of every hand, as bits, with the leader of the trick : enough to know what is left
+    /// to win at the beginning of a trick.
+    fn key(&self) -> ([u32; 4], Position) {
+        let mut masks = [0; 4];
+        for (mask, position) in masks.iter_mut().zip(Position::iter()) {
+            *mask = self.hands[position].cards().bits();
+        }
+        (masks, self.next)
+    }
+}
+
+/// Deal played perfectly from a layout.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Solution {
+    points: Points,
+    values: Vec<(Card, u64)>,
+}
+
+impl Solution {
+    /// Points each team still takes, cards on the table and dix de der included.
+    pub const fn points(&self) -> Points {
+        self.points
+    }
+    /// Points the team of the player to play takes with each of its legal cards.
+    pub fn values(&self) -> &[(Card, u64)] {
+        &self.values
+    }
+    /// Optimal cards of the player to play.
+    pub fn best(&self) -> Vec<Card> {
+        let best = self.values.iter().map(|(_, value)| *value).max();
+        self.values
+            .iter()
+            .filter(|(_, value)| Some(*value) == best)
+            .map(|(card, _)| *card)
+            .collect()
+    }
+}
+
+/// Solves `layout` double dummy, every hand being known.
+pub fn solve(layout: &Layout) -> Result<Solution, BeloteErrorKind> {
+    layout.check()?;
+    let values = Solver::new().card_values(layout)?;
+    let team = layout.next().team();
+    let best = values
+        .iter()
+        .map(|(_, value)| *value)
+        .max()
+        .unwrap_or_default();
+    let mut points = Points::default();
+    points[team] = best;
+    points[team.other()] = layout.remaining_points().saturating_sub(best);
+    Ok(Solution { points, values })
+}
+
+/// Double dummy solver : alpha-beta search over the remaining cards, bounds being kept for the
+/// beginning of each trick already met.
+#[derive(Debug, Default)]
+pub struct Solver {
+    table: HashMap<([u32; 4], Position), (i64, i64)>,
+    nodes: u64,
+}
+
+impl Solver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Positions searched so far.
+    pub const fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// Points the team of the player to play still wins when everybody plays perfectly.
+    pub fn value(&mut self, layout: &Layout) -> Result<u64, BeloteErrorKind> {
+        let north_south = self.search(layout, i64::MIN, i64::MAX)?;
+        Ok(Self::for_team(layout, layout.next().team(), north_south))
+    }
+
+    /// Points the team of the player to play still wins with each of its legal cards, the rest
+    /// of the deal being played perfectly.
+    pub fn card_values(&mut self, layout: &Layout) -> Result<Vec<(Card, u64)>, BeloteErrorKind> {
+        let team = layout.next().team();
+        let mut values = Vec::new();
+        for card in layout.choices()? {
+            let (child, trick) = layout.play(card)?;
+            let won = match trick {
+                Some((winner, points)) if winner.team() == Team::NorthSouth => points,
+                _ => 0,
+            };
+            let north_south =
+                i64::try_from(won).unwrap_or_default() + self.search(&child, i64::MIN, i64::MAX)?;
+            values.push((card, Self::for_team(layout, team, north_south)));
+        }
+        Ok(values)
+    }
+
+    fn for_team(layout: &Layout, team: Team, north_south: i64) -> u64 {
+        let north_south = u64::try_from(north_south).unwrap_or_default();
+        match team {
+            Team::NorthSouth => north_south,
+            Team::WestEast => layout.remaining_points().saturating_sub(north_south),
+        }
+    }
+
+    /// Points won by North / South from `layout`, North / South maximizing them and West / East
+    /// minimizing them.
+    fn search(
+        &mut self,
+        layout: &Layout,
+        mut alpha: i64,
+        mut beta: i64,
+    ) -> Result<i64, BeloteErrorKind> {
+        self.nodes += 1;
+        if layout.is_over() {
+            return Ok(0);
+        }
+        let key = layout.turn.is_first().then(|| layout.key());
+        let (alpha_start, beta_start) = (alpha, beta);
+        if let Some(key) = &key {
+            if let Some((lower, upper)) = self.table.get(key) {
+                if lower >= &beta || lower == upper {
+                    return Ok(*lower);
+                }
+                if upper <= &alpha {
+                    return Ok(*upper);
+                }
+                alpha = alpha.max(*lower);
+                beta = beta.min(*upper);
+            }
+        }
+
+        let maximizing = layout.next().team() == Team::NorthSouth;
+        let trump = layout.trump();
+        let beating = layout
+            .turn
+            .master_card()
+            .map_or(CardSet::ALL, |master| CardSet::beating(master, trump));
+        let mut choices = [None; MAX_CARDS_BY_PLAYER];
+        for (choice, card) in choices.iter_mut().zip(layout.choices()?) {
+            *choice = Some(card);
+        }
+        choices.sort_unstable_by_key(|card| {
+            std::cmp::Reverse(card.map(|card| {
+                (
+                    beating.contains(card),
+                    card.points(trump),
+                    card.power(trump),
+                )
+            }))
+        });
+
+        let mut best = if maximizing { i64::MIN } else { i64::MAX };
+        for card in choices.into_iter().flatten() {
+            let (child, trick) = layout.play(card)?;
+            let won = match trick {
+                Some((winner, points)) if winner.team() == Team::NorthSouth => {
+                    i64::try_from(points).unwrap_or_default()
+                }
+                _ => 0,
+            };
+            let value =
+                won + self.search(&child, alpha.saturating_sub(won), beta.saturating_sub(won))?;
+            if maximizing {
+                best = best.max(value);
+                alpha = alpha.max(value);
+            } else {
+                best = best.min(value);
+                beta = beta.min(value);
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        if let Some(key) = key {
+            let (mut lower, mut upper) = self.table.get(&key).copied().unwrap_or((0, i64::MAX));
+            if best <= alpha_start {
+                upper = upper.min(best);
+            } else if best >= beta_start {
+                lower = lower.max(best);
+            } else {
+                lower = best;
+                upper = best;
+            }
+            self.table.insert(key, (lower, upper));
+        }
+        Ok(best)
+    }
+}
+
+#[test]
+fn solver_tests() {
+    use crate::card::Color;
+    use crate::stack::Stack;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::str::FromStr;
+
+    // Last trick : North leads the heart jack and wins everything, dix de der included.
+    let mut hands = Hands::default();
+    for (position, card) in Position::iter().zip(["♥J", "♥7", "♠11", "♣10"]) {
+        if let Ok(card) = Card::from_str(card) {
+            let _ = hands[position].take(card);
+        }
+    }
+    let trump = Trump::Color(Color::Heart);
+    let Ok(layout) = Layout::new(hands, &[], Position::North, trump, RuleSet::default()) else {
+        panic!("layout should be valid");
+    };
+    assert_eq!(Solver::new().value(&layout), Ok(20 + 11 + 10 + 10));
+    let Some(Ok((after, _))) = hands[Position::North]
+        .into_iter()
+        .next()
+        .map(|card| layout.play(card))
+    else {
+        panic!("north should play");
+    };
+    assert_eq!(Solver::new().value(&after), Ok(0));
+
+    // Last 4 tricks of random deals.
+    for seed in 0..5 {
+        let mut stack = Stack::random(&mut StdRng::seed_from_u64(seed));
+        let mut hands = Hands::default();
+        for position in Position::iter() {
+            for _ in 0..4 {
+                let Ok(card) = stack.give_card() else {
+                    panic!("stack should have 32 cards");
+                };
+                let _ = hands[position].take(card);
+            }
+        }
+        let Ok(layout) = Layout::new(hands, &[], Position::North, trump, RuleSet::default()) else {
+            panic!("layout should be valid");
+        };
+        let mut solver = Solver::new();
+        let Ok(value) = solver.value(&layout) else {
+            panic!("deal should be solved");
+        };
+        let Ok(values) = solver.card_values(&layout) else {
+            panic!("cards should be solved");
+        };
+        assert_eq!(values.iter().map(|(_, value)| *value).max(), Some(value));
+
+        let Ok(solution) = solve(&layout) else {
+            panic!("layout should be valid");
+        };
+        assert_eq!(solution.points()[Team::NorthSouth], value);
+        assert_eq!(
+            solution.points()[Team::NorthSouth] + solution.points()[Team::WestEast],
+            layout.remaining_points()
+        );
+        assert!(solution
+            .best()
+            .iter()
+            .all(|card| values.contains(&(*card, value))));
+    }
+
+    // North holding a card nobody else has to follow.
+    let mut hands = Hands::default();
+    if let Ok(card) = Card::from_str("♥11") {
+        let _ = hands[Position::North].take(card);
+    }
+    let Ok(uneven) = Layout::new(hands, &[], Position::North, trump, RuleSet::default()) else {
+        panic!("layout should be built, then refused by the solver");
+    };
+    assert!(solve(&uneven).is_err());
+
+    // A leader still holding 8 cards cannot have led a trick.
+    let mut hands = Hands::default();
+    for card in Card::all().filter(|card| card.color() == Color::Heart) {
+        let _ = hands[Position::North].take(card);
+    }
+    let Ok(card) = Card::from_str("♥7") else {
+        panic!("bad card");
+    };
+    let trick = [(Position::North, card)];
+    assert!(Layout::new(hands, &trick, Position::North, trump, RuleSet::default()).is_err());
+
+    // North led the diamond 7 : East plays next, and the 7 cannot still be held.
+    let card = |card: &str| Card::from_str(card).unwrap_or_else(|_| panic!("bad card {card}"));
+    let mut hands = Hands::default();
+    for (position, cards) in [
+        (Position::North, ["♥J", "♥7", "♥8"].as_slice()),
+        (Position::East, &["♠11", "♠10", "♠K", "♠7"]),
+        (Position::South, &["♣11", "♣10", "♣K", "♣7"]),
+        (Position::West, &["♦11", "♦10", "♦K", "♦Q"]),
+    ] {
+        for name in cards {
+            let _ = hands[position].take(card(name));
+        }
+    }
+    let rules = RuleSet::default();
+    let led = [(Position::North, card("♦7"))];
+    assert!(Layout::new(hands, &led, Position::North, trump, rules).is_ok());
+    let skipped = [(Position::North, card("♦7")), (Position::South, card("♦9"))];
+    assert!(Layout::new(hands, &skipped, Position::North, trump, rules).is_err());
+    let held = [(Position::North, card("♦7")), (Position::East, card("♠K"))];
+    assert!(Layout::new(hands, &held, Position::North, trump, rules).is_err());
+}
@@ -3,11 +3,13 @@ use crate::constants::MAX_CARDS;
 use crate::errors::{BeloteErrorKind, ErrOnSome};
 use derive_more::{Index, IntoIterator};
 use rand::seq::SliceRandom;
-use rand::{thread_rng, Rng};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use tinyvec::ArrayVec;
 
-#[derive(Default, Clone, Debug, Index, Copy)]
+/// The deck, cards are dealt from its end.
+#[derive(Default, Clone, Debug, Index, Copy, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Stack(ArrayVec<[Option<Card>; MAX_CARDS]>);
 
 impl fmt::Display for Stack {
@@ -56,14 +58,12 @@ impl Stack {
         cards.push(Card::new(Color::Diamond, Value::_7));
         Self(cards)
     }
-    pub fn random() -> Self {
-        let mut rng = thread_rng();
+    pub fn random(rng: &mut impl Rng) -> Self {
         let mut new_stack = Self::new();
-        new_stack.0.shuffle(&mut rng);
+        new_stack.0.shuffle(rng);
         new_stack
     }
-    pub fn cut(&mut self) {
-        let mut rng = thread_rng();
+    pub fn cut(&mut self, rng: &mut impl Rng) {
         let len = self.0.len();
         self.0.rotate_left(rng.gen_range(0..len));
     }
@@ -0,0 +1,242 @@
+use crate::bidding::Bid;
+use crate::errors::BeloteErrorKind;
+use crate::event::{GameEvent, Observer};
+use crate::points::Points;
+use crate::team::Team;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use strum::IntoEnumIterator;
+
+fn float(count: u64) -> f64 {
+    f64::from(u32::try_from(count).unwrap_or(u32::MAX))
+}
+
+/// `part` out of `whole`, 0 when `whole` is.
+pub fn ratio(part: u64, whole: u64) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        float(part) / float(whole)
+    }
+}
+
+/// Bidding round in which the deal being played was taken.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Round {
+    First,
+    Second,
+    Auction,
+}
+
+/// Results of many deals, aggregated from their events.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Statistics {
+    deals: u64,
+    taken: u64,
+    /// Takes of the returned card, per trump name.
+    first_round: BTreeMap<String, u64>,
+    /// Takes of another trump at the second round, per trump name.
+    second_round: BTreeMap<String, u64>,
+    /// Coinche contracts, per trump name.
+    auction: BTreeMap<String, u64>,
+    outcomes: BTreeMap<String, u64>,
+    belotes: u64,
+    points: Points,
+    /// Time spent playing, in seconds.
+    seconds: f64,
+    #[serde(skip)]
+    round: Option<Round>,
+}
+
+impl Statistics {
+    pub const fn deals(&self) -> u64 {
+        self.deals
+    }
+    /// Deals dealt again because nobody took.
+    pub const fn redeals(&self) -> u64 {
+        self.deals.saturating_sub(self.taken)
+    }
+    pub fn redeal_rate(&self) -> f64 {
+        ratio(self.redeals(), self.deals)
+    }
+    pub const fn first_round(&self) -> &BTreeMap<String, u64> {
+        &self.first_round
+    }
+    pub const fn second_round(&self) -> &BTreeMap<String, u64> {
+        &self.second_round
+    }
+    pub const fn auction(&self) -> &BTreeMap<String, u64> {
+        &self.auction
+    }
+    /// Scored deals, per outcome.
+    pub const fn outcomes(&self) -> &BTreeMap<String, u64> {
+        &self.outcomes
+    }
+    /// Deals played until their score.
+    pub fn scored(&self) -> u64 {
+        self.outcomes.values().sum()
+    }
+    /// Share of the scored deals ending with `outcome`.
+    pub fn frequency(&self, outcome: &str) -> f64 {
+        ratio(
+            self.outcomes.get(outcome).copied().unwrap_or_default(),
+            self.scored(),
+        )
+    }
+    /// Scored deals where belote was declared.
+    pub const fn belotes(&self) -> u64 {
+        self.belotes
+    }
+    /// Points scored by each team over all deals.
+    pub const fn points(&self) -> Points {
+        self.points
+    }
+    pub fn average_points(&self, team: Team) -> f64 {
+        ratio(self.points[team], self.scored())
+    }
+    pub const fn seconds(&self) -> f64 {
+        self.seconds
+    }
+    pub const fn set_seconds(&mut self, seconds: f64) {
+        self.seconds = seconds;
+    }
+    pub fn deals_per_second(&self) -> f64 {
+        if self.seconds > 0.0 {
+            float(self.deals) / self.seconds
+        } else {
+            0.0
+        }
+    }
+
+    /// Adds the deals of `other`, played at the same time.
+    pub fn merge(&mut self, other: &Self) {
+        let add = |into: &mut BTreeMap<String, u64>, from: &BTreeMap<String, u64>| {
+            for (key, count) in from {
+                *into.entry(key.clone()).or_default() += count;
+            }
+        };
+        self.deals += other.deals;
+        self.taken += other.taken;
+        add(&mut self.first_round, &other.first_round);
+        add(&mut self.second_round, &other.second_round);
+        add(&mut self.auction, &other.auction);
+        add(&mut self.outcomes, &other.outcomes);
+        self.belotes += other.belotes;
+        for team in Team::iter() {
+            self.points[team] += other.points[team];
+        }
+        self.seconds = self.seconds.max(other.seconds);
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), BeloteErrorKind> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| BeloteErrorKind::InvalidRecord(e.to_string()))?;
+        fs::write(path, json).map_err(|e| BeloteErrorKind::InvalidRecord(e.to_string()))
+    }
+}
+
+impl Observer for Statistics {
+    fn notify(&mut self, event: &GameEvent) {
+        match *event {
+            GameEvent::DealStarted { .. } => {
+                self.deals += 1;
+                self.round = None;
+            }
+            GameEvent::BidMade { bid, .. } => {
+                self.round = match bid {
+                    Bid::Take => Some(Round::First),
+                    Bid::Color(_) => Some(Round::Second),
+                    Bid::Announce { .. } => Some(Round::Auction),
+                    _ => self.round,
+                };
+            }
+            GameEvent::TrumpChosen { trump, .. } => {
+                self.taken += 1;
+                let takes = match self.round {
+                    Some(Round::First) => &mut self.first_round,
+                    Some(Round::Second) => &mut self.second_round,
+                    Some(Round::Auction) | None => &mut self.auction,
+                };
+                *takes.entry(trump.name().to_string()).or_default() += 1;
+            }
+            GameEvent::DealScored {
+                belote_rebelote,
+                outcome,
+                points,
+                ..
+            } => {
+                *self.outcomes.entry(format!("{outcome:?}")).or_default() += 1;
+                if belote_rebelote.is_some() {
+                    self.belotes += 1;
+                }
+                for team in Team::iter() {
+                    self.points[team] += points[team];
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let takes = |takes: &BTreeMap<String, u64>| {
+            takes
+                .iter()
+                .map(|(trump, count)| format!("{trump} {count}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        writeln!(
+            f,
+            "Deals : {} ({:.1}% dealt again)",
+            self.deals,
+            100.0 * self.redeal_rate()
+        )?;
+        if !self.first_round.is_empty() || !self.second_round.is_empty() {
+            writeln!(f, "First round takes : {}", takes(&self.first_round))?;
+            writeln!(f, "Second round takes : {}", takes(&self.second_round))?;
+        }
+        if !self.auction.is_empty() {
+            writeln!(f, "Contracts : {}", takes(&self.auction))?;
+        }
+        for outcome in ["Made", "Dedans", "Litige", "Capot", "DefenseCapot"] {
+            writeln!(f, "{outcome} : {:.1}%", 100.0 * self.frequency(outcome))?;
+        }
+        writeln!(
+            f,
+            "Belote : {:.1}%",
+            100.0 * ratio(self.belotes, self.scored())
+        )?;
+        for team in Team::iter() {
+            writeln!(
+                f,
+                "{team} : {:.1} points per deal",
+                self.average_points(team)
+            )?;
+        }
+        write!(f, "{:.1} deals per second", self.deals_per_second())
+    }
+}
+
+#[test]
+fn statistics_tests() {
+    use crate::card::{Color, Trump};
+    use crate::position::Position;
+
+    let mut statistics = Statistics::default();
+    statistics.notify(&GameEvent::BidMade {
+        position: Position::North,
+        bid: Bid::Take,
+    });
+    statistics.notify(&GameEvent::TrumpChosen {
+        taker: Position::North,
+        trump: Trump::Color(Color::Heart),
+    });
+    assert_eq!(statistics.first_round().get("heart"), Some(&1));
+    let json = serde_json::to_string(&statistics).unwrap_or_default();
+    assert!(json.contains(r#""first_round":{"heart":1}"#));
+}
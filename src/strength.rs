@@ -0,0 +1,182 @@
+use crate::card::{Card, Color, Trump, Value};
+use crate::hand::Hand;
+use std::fmt;
+use strum::IntoEnumIterator;
+
+/// Points the taker team is expected to make to be worth taking : more than half of the deal.
+pub const TAKE: u64 = 82;
+
+/// Points expected from the partner and the tricks left over by the defense, whatever the hand.
+const BASE: u64 = 10;
+
+/// How a hand, the returned card included, would do with a given trump.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Strength {
+    trump: Trump,
+    trumps: usize,
+    belote: bool,
+    expected: u64,
+}
+
+impl Strength {
+    pub const fn trump(&self) -> Trump {
+        self.trump
+    }
+    /// Trumps held.
+    pub const fn trumps(&self) -> usize {
+        self.trumps
+    }
+    /// Is the king and queen of trump held ?
+    pub const fn belote(&self) -> bool {
+        self.belote
+    }
+    /// Points the taker team may expect, belote included.
+    pub const fn expected(&self) -> u64 {
+        self.expected
+    }
+    pub const fn take(&self) -> bool {
+        self.expected >= TAKE
+    }
+}
+
+impl fmt::Display for Strength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} : {} trumps, {} points expected, {}",
+            self.trump,
+            self.trumps,
+            self.expected,
+            if self.take() { "take" } else { "pass" }
+        )?;
+        if self.belote {
+            write!(f, " (belote)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Strength of `hand` and `returned`, when given, if `trump` is chosen.
+///
+/// Trumps count for their points and their length, the jack and a guarded nine being masters.
+/// Outside trumps, aces and tens behind them are probable tricks, short colors let trumps cut.
+pub fn evaluate(hand: &Hand, returned: Option<Card>, trump: Trump) -> Strength {
+    let cards: Vec<Card> = hand.into_iter().chain(returned).collect();
+    let held = |color: Color, value: Value| {
+        Card::new(color, value).is_some_and(|card| cards.contains(&card))
+    };
+    let length = |color: Color| cards.iter().filter(|card| card.color() == color).count();
+    let trumps = cards
+        .iter()
+        .filter(|card| trump.is_trump(card.color()))
+        .count();
+    let belote = trump
+        .color()
+        .is_some_and(|color| held(color, Value::King) && held(color, Value::Queen));
+
+    let mut expected = BASE;
+    for color in Color::iter() {
+        let count = length(color) as u64;
+        let points: u64 = cards
+            .iter()
+            .filter(|card| card.color() == color)
+            .map(|card| card.points(trump))
+            .sum();
+        expected += match trump {
+            Trump::Color(trump_color) if trump_color == color => {
+                let jack = held(color, Value::Jack);
+                let nine = held(color, Value::_9) && (jack || count >= 3);
+                points + 8 * count + if jack { 10 } else { 0 } + if nine { 5 } else { 0 }
+            }
+            Trump::Color(_) => {
+                let ace = held(color, Value::As);
+                let ten = held(color, Value::_10);
+                let cut = if trumps >= 2 {
+                    match count {
+                        0 => 8,
+                        1 => 4,
+                        _ => 0,
+                    }
+                } else {
+                    0
+                };
+                (if ace { 12 } else { 0 })
+                    + match (ace, ten) {
+                        (true, true) => 8,
+                        (false, true) if count >= 2 => 3,
+                        _ => 0,
+                    }
+                    + cut
+            }
+            Trump::NoTrump => {
+                let ace = held(color, Value::As);
+                let ten = held(color, Value::_10);
+                let king = held(color, Value::King);
+                (if ace { 22 } else { 0 })
+                    + if ace && ten { 12 } else { 0 }
+                    + if ace && ten && king { 6 } else { 0 }
+                    + if ace { 4 * count.saturating_sub(3) } else { 0 }
+            }
+            Trump::AllTrump => {
+                let jack = held(color, Value::Jack);
+                let nine = held(color, Value::_9);
+                points / 2
+                    + if jack { 25 } else { 0 }
+                    + match (jack, nine) {
+                        (true, true) => 12,
+                        (false, true) if count >= 2 => 6,
+                        _ => 0,
+                    }
+            }
+        };
+    }
+    if belote {
+        expected += 20;
+    }
+    Strength {
+        trump,
+        trumps,
+        belote,
+        expected,
+    }
+}
+
+/// Strength of `hand` and `returned` for every trump, the best first.
+pub fn advise(hand: &Hand, returned: Option<Card>) -> Vec<Strength> {
+    let mut strengths: Vec<Strength> = Trump::all()
+        .map(|trump| evaluate(hand, returned, trump))
+        .collect();
+    strengths.sort_by_key(|strength| std::cmp::Reverse(strength.expected()));
+    strengths
+}
+
+#[test]
+fn strength_tests() {
+    use std::str::FromStr;
+
+    let hand = |cards: &[&str]| {
+        let mut hand = Hand::default();
+        for card in cards {
+            if let Ok(card) = Card::from_str(card) {
+                let _ = hand.take(card);
+            }
+        }
+        hand
+    };
+    let hearts = Trump::Color(Color::Heart);
+    let returned = Card::from_str("♥J").ok();
+
+    let strong = evaluate(&hand(&["♥9", "♥11", "♠11", "♦7", "♣8"]), returned, hearts);
+    assert_eq!(strong.trumps(), 3);
+    assert!(strong.take(), "{strong}");
+
+    let weak = evaluate(&hand(&["♠7", "♠8", "♦7", "♦9", "♣8"]), returned, hearts);
+    assert!(!weak.take(), "{weak}");
+    assert!(weak.expected() < strong.expected());
+
+    let belote = evaluate(&hand(&["♥K", "♥Q", "♠7", "♦7", "♣8"]), returned, hearts);
+    assert!(belote.belote());
+
+    let advice = advise(&hand(&["♠11", "♦11", "♣11", "♠10", "♦10"]), None);
+    assert_eq!(advice.first().map(Strength::trump), Some(Trump::NoTrump));
+}
@@ -1,7 +1,8 @@
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use strum_macros::EnumIter;
 
-#[derive(Eq, PartialEq, Clone, Copy, Debug, EnumIter)]
+#[derive(Eq, PartialEq, Clone, Copy, Debug, EnumIter, Serialize, Deserialize)]
 pub enum Team {
     NorthSouth,
     WestEast,
@@ -0,0 +1,372 @@
+use crate::announce::Announce;
+use crate::card::{Card, Color, Trump, Value};
+use crate::card_set::CardSet;
+use crate::constants::{MAX_CARDS_BY_PLAYER, MAX_PLAYERS};
+use crate::event::GameEvent;
+use crate::hands::Hands;
+use crate::position::Position;
+use crate::rules::RuleSet;
+use crate::view::PlayerView;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use strum::IntoEnumIterator;
+
+/// Deals tried before forgetting what was inferred.
+const ATTEMPTS: usize = 100;
+
+/// Memory of a deal as seen from the table : the cards played trick after trick and, for each
+/// seat, the cards it may still hold.
+///
+/// A seat loses the color it fails to follow, the trumps it should have cut or gone up with, and
+/// the cards another seat showed : the returned card held by the taker, announces and belote.
+#[derive(Debug, Clone)]
+pub struct Tracker {
+    rules: RuleSet,
+    trump: Option<Trump>,
+    returned: Option<Card>,
+    trick: Vec<(Position, Card)>,
+    played: Vec<(Position, Card)>,
+    /// Cards played, whoever played them.
+    gone: CardSet,
+    possible: [CardSet; MAX_PLAYERS],
+    /// Cards each seat showed and still holds.
+    known: [CardSet; MAX_PLAYERS],
+    belote: Option<Position>,
+}
+
+impl Default for Tracker {
+    fn default() -> Self {
+        Self {
+            rules: RuleSet::default(),
+            trump: None,
+            returned: None,
+            trick: Vec::new(),
+            played: Vec::new(),
+            gone: CardSet::EMPTY,
+            possible: [CardSet::ALL; MAX_PLAYERS],
+            known: [CardSet::EMPTY; MAX_PLAYERS],
+            belote: None,
+        }
+    }
+}
+
+impl Tracker {
+    pub const fn rules(&self) -> RuleSet {
+        self.rules
+    }
+    pub const fn trump(&self) -> Option<Trump> {
+        self.trump
+    }
+    /// Cards of the current trick, in the order they were played.
+    pub fn trick(&self) -> &[(Position, Card)] {
+        &self.trick
+    }
+    /// Every card played during the deal, in order.
+    pub fn played(&self) -> &[(Position, Card)] {
+        &self.played
+    }
+    /// Cards played so far, whoever played them.
+    pub const fn gone(&self) -> CardSet {
+        self.gone
+    }
+    /// Leader of the current trick, `position` when it is about to lead.
+    pub fn leader(&self, position: Position) -> Position {
+        self.trick.first().map_or(position, |(leader, _)| *leader)
+    }
+    /// Cards `position` may still hold.
+    pub const fn possible(&self, position: Position) -> CardSet {
+        self.possible[position as usize]
+    }
+    /// Cards `position` showed and still holds.
+    pub const fn known(&self, position: Position) -> CardSet {
+        self.known[position as usize]
+    }
+    pub const fn is_void(&self, position: Position, color: Color) -> bool {
+        self.possible(position).of_color(color).is_empty()
+    }
+    /// Cards `position` still holds.
+    pub fn left(&self, position: Position) -> usize {
+        let played = self.played.iter().filter(|(by, _)| *by == position).count();
+        MAX_CARDS_BY_PLAYER.saturating_sub(played)
+    }
+
+    /// Removes `forbidden` from what `position` may hold, but the cards it showed.
+    fn forbid(&mut self, position: Position, forbidden: CardSet) {
+        self.possible[position as usize] -= forbidden - self.known(position);
+    }
+
+    fn show(&mut self, position: Position, card: Card) {
+        if self.gone.contains(card) || !self.known[position as usize].insert(card) {
+            return;
+        }
+        for other in Position::iter().filter(|other| *other != position) {
+            self.possible[other as usize].remove(card);
+        }
+    }
+
+    /// Player and card winning the current trick.
+    pub fn winning(&self, trump: Trump) -> Option<(Position, Card)> {
+        let mut winning = *self.trick.first()?;
+        for (position, card) in self.trick.iter().skip(1) {
+            if !winning.1.master(*card, trump) {
+                winning = (*position, *card);
+            }
+        }
+        Some(winning)
+    }
+
+    /// Cards `position` has shown not to hold by playing `card` on the current trick.
+    fn infer(&mut self, position: Position, card: Card) {
+        let Some(trump) = self.trump else {
+            return;
+        };
+        let (Some((_, led)), Some((master_position, master))) =
+            (self.trick.first(), self.winning(trump))
+        else {
+            return;
+        };
+        let called = led.color();
+        let beating = CardSet::beating(master, trump);
+        if trump.is_trump(called) {
+            if card.color() != called {
+                self.forbid(position, CardSet::color(called));
+            } else if !beating.contains(card) {
+                self.forbid(position, CardSet::color(called) & beating);
+            }
+            return;
+        }
+        if card.color() == called {
+            return;
+        }
+        self.forbid(position, CardSet::color(called));
+        let partner_master =
+            master_position == position.partner() && !self.rules.overtrump_on_partner();
+        let is_trump = trump.is_trump(card.color());
+        if !(is_trump && beating.contains(card)) && (is_trump || !partner_master) {
+            self.forbid(position, CardSet::trumps(trump) & beating);
+        }
+    }
+
+    /// Tracker having seen what `view` shows of the deal.
+    pub fn from_view(view: &PlayerView) -> Self {
+        let mut tracker = Self {
+            rules: view.rules(),
+            returned: view.card_returned(),
+            ..Self::default()
+        };
+        if let (Some(taker), Some(trump)) = (view.taker(), view.trump()) {
+            tracker.choose_trump(taker, trump);
+        }
+        for (position, announce) in view.announces() {
+            tracker.declare(*position, *announce);
+        }
+        // Belote is announced with the first trump king or queen of its holder.
+        let mut belote = view.belote();
+        for (index, (position, card)) in view.played().iter().enumerate() {
+            let belote_card = view.trump().and_then(Trump::color) == Some(card.color())
+                && matches!(card.value(), Value::King | Value::Queen);
+            if belote_card && belote == Some(*position) {
+                tracker.belote = belote.take();
+            }
+            tracker.play(*position, *card);
+            if index % MAX_PLAYERS == MAX_PLAYERS - 1 {
+                tracker.trick.clear();
+            }
+        }
+        tracker
+    }
+
+    pub fn notify(&mut self, event: &GameEvent) {
+        match *event {
+            GameEvent::DealStarted { rules, .. } => {
+                *self = Self {
+                    rules,
+                    ..Self::default()
+                };
+            }
+            GameEvent::CardReturned { card } => self.returned = Some(card),
+            GameEvent::TrumpChosen { taker, trump } => self.choose_trump(taker, trump),
+            GameEvent::AnnounceDeclared { position, announce } => self.declare(position, announce),
+            GameEvent::Belote { position } => self.belote = Some(position),
+            GameEvent::CardPlayed { position, card } => self.play(position, card),
+            GameEvent::TrickWon { .. } => self.trick.clear(),
+            _ => {}
+        }
+    }
+
+    fn choose_trump(&mut self, taker: Position, trump: Trump) {
+        self.trump = Some(trump);
+        if let Some(card) = self.returned {
+            self.show(taker, card);
+        }
+    }
+
+    fn declare(&mut self, position: Position, announce: Announce) {
+        for card in announce.cards() {
+            self.show(position, card);
+        }
+    }
+
+    fn play(&mut self, position: Position, card: Card) {
+        self.infer(position, card);
+        for possible in &mut self.possible {
+            possible.remove(card);
+        }
+        for known in &mut self.known {
+            known.remove(card);
+        }
+        self.gone.insert(card);
+        self.trick.push((position, card));
+        self.played.push((position, card));
+        if self.belote.take_if(|holder| *holder == position).is_some() {
+            let pair = match card.value() {
+                Value::King => Value::Queen,
+                _ => Value::King,
+            };
+            if let Some(other) = Card::new(card.color(), pair) {
+                self.show(position, other);
+            }
+        }
+    }
+
+    /// Deals the cards `position`, holding `hand`, cannot see to the other players, `None` when
+    /// it fails.
+    fn deal(
+        &self,
+        position: Position,
+        hand: CardSet,
+        inferred: bool,
+        rng: &mut dyn RngCore,
+    ) -> Option<Hands> {
+        let mut hands = Hands::default();
+        for card in hand {
+            hands[position].take(card).ok()?;
+        }
+        let mut seen = hand | self.gone;
+        for holder in Position::iter() {
+            if holder != position {
+                for card in self.known(holder) - hand {
+                    hands[holder].take(card).ok()?;
+                }
+            }
+            seen |= self.known(holder);
+        }
+        let missing =
+            |hands: &Hands, seat: Position| self.left(seat).saturating_sub(hands[seat].len());
+        let can_hold = |seat: Position, card: &Card| {
+            seat != position && (!inferred || self.possible(seat).contains(*card))
+        };
+        let mut unseen: Vec<Card> = (!seen).into_iter().collect();
+        unseen.shuffle(rng);
+        unseen.sort_by_key(|card| {
+            Position::iter()
+                .filter(|seat| can_hold(*seat, card))
+                .count()
+        });
+        for card in unseen {
+            let seats: Vec<Position> = Position::iter()
+                .filter(|seat| can_hold(*seat, &card) && missing(&hands, *seat) > 0)
+                .collect();
+            let seat = seats.get(rng.gen_range(0..seats.len().max(1)))?;
+            hands[*seat].take(card).ok()?;
+        }
+        Position::iter()
+            .all(|seat| missing(&hands, seat) == 0)
+            .then_some(hands)
+    }
+
+    /// A deal consistent with what `position`, holding `hand`, saw, the inferences being
+    /// forgotten if none is found.
+    pub fn sample(
+        &self,
+        position: Position,
+        hand: CardSet,
+        rng: &mut dyn RngCore,
+    ) -> Option<Hands> {
+        (0..ATTEMPTS)
+            .find_map(|_| self.deal(position, hand, true, rng))
+            .or_else(|| self.deal(position, hand, false, rng))
+    }
+}
+
+#[test]
+fn tracker_tests() {
+    use crate::order::Order;
+    use crate::stack::Stack;
+    use crate::trick::Trick;
+    use crate::variant::Variant;
+    use std::str::FromStr;
+
+    let card = |card: &str| Card::from_str(card).unwrap_or_else(|_| panic!("bad card {card}"));
+    let mut tracker = Tracker::default();
+    let events = [
+        GameEvent::DealStarted {
+            number: 0,
+            order: Order::default(),
+            stack: Stack::new(),
+            litige: 0,
+            variant: Variant::Belote,
+            rules: RuleSet::default(),
+        },
+        GameEvent::CardReturned { card: card("♦K") },
+        GameEvent::TrumpChosen {
+            taker: Position::South,
+            trump: Trump::Color(Color::Heart),
+        },
+        GameEvent::CardPlayed {
+            position: Position::North,
+            card: card("♠11"),
+        },
+        GameEvent::CardPlayed {
+            position: Position::East,
+            card: card("♥9"),
+        },
+        // South cannot overtrump the 9 of its opponent : no spade, no jack of trump.
+        GameEvent::CardPlayed {
+            position: Position::South,
+            card: card("♦7"),
+        },
+        // West discards on its partner : no spade only.
+        GameEvent::CardPlayed {
+            position: Position::West,
+            card: card("♣7"),
+        },
+        GameEvent::TrickWon {
+            trick: Trick::new(
+                1,
+                [
+                    (Position::North, card("♠11")),
+                    (Position::East, card("♥9")),
+                    (Position::South, card("♦7")),
+                    (Position::West, card("♣7")),
+                ],
+                Position::East,
+            ),
+            points: 25,
+        },
+    ];
+    for event in &events {
+        tracker.notify(event);
+    }
+    for position in [Position::East, Position::South, Position::West] {
+        assert!(tracker.is_void(position, Color::Spade));
+    }
+    assert!(!tracker.is_void(Position::North, Color::Spade));
+    assert!(!tracker.is_void(Position::West, Color::Heart));
+    assert!(!tracker.possible(Position::South).contains(card("♥J")));
+    assert!(tracker.possible(Position::South).contains(card("♥8")));
+    assert_eq!(tracker.known(Position::South), CardSet::single(card("♦K")));
+    assert!(!tracker.possible(Position::North).contains(card("♦K")));
+    assert_eq!(tracker.left(Position::West), 7);
+
+    // South discards on a club of its opponent : no club, no trump at all.
+    for (position, played) in [(Position::East, "♣11"), (Position::South, "♦8")] {
+        tracker.notify(&GameEvent::CardPlayed {
+            position,
+            card: card(played),
+        });
+    }
+    assert!(tracker.is_void(Position::South, Color::Club));
+    assert!(tracker.is_void(Position::South, Color::Heart));
+    assert!(tracker.possible(Position::South).contains(card("♦K")));
+}
@@ -0,0 +1,104 @@
+use crate::card::{Card, Color, Trump};
+use crate::constants::MAX_PLAYERS;
+use crate::position::Position;
+use crate::team::Team;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A trick won : its cards in the order they were played, the leader first, and its winner.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Trick {
+    number: u64,
+    cards: [(Position, Card); MAX_PLAYERS],
+    winner: Position,
+}
+
+impl Trick {
+    pub const fn new(
+        number: u64,
+        cards: [(Position, Card); MAX_PLAYERS],
+        winner: Position,
+    ) -> Self {
+        Self {
+            number,
+            cards,
+            winner,
+        }
+    }
+    pub const fn number(&self) -> u64 {
+        self.number
+    }
+    /// Seat which played the first card.
+    pub const fn leader(&self) -> Position {
+        self.cards[0].0
+    }
+    /// Cards of the trick, in the order they were played.
+    pub const fn cards(&self) -> &[(Position, Card); MAX_PLAYERS] {
+        &self.cards
+    }
+    /// Color of the first card, the one to follow.
+    pub const fn called_color(&self) -> Color {
+        self.cards[0].1.color()
+    }
+    pub const fn winner(&self) -> Position {
+        self.winner
+    }
+    pub const fn team(&self) -> Team {
+        self.winner.team()
+    }
+    /// Card played by `position`.
+    pub fn card(&self, position: Position) -> Option<Card> {
+        self.cards
+            .iter()
+            .find(|(played_by, _)| *played_by == position)
+            .map(|(_, card)| *card)
+    }
+    pub fn points(&self, trump: Trump) -> u64 {
+        self.cards.iter().map(|(_, card)| card.points(trump)).sum()
+    }
+}
+
+impl fmt::Display for Trick {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Trick {} :", self.number)?;
+        for (position, card) in self.cards {
+            write!(f, " {position} {card},")?;
+        }
+        write!(f, " won by {}", self.winner)
+    }
+}
+
+#[test]
+fn trick_tests() {
+    use crate::card::Color;
+    use crate::order::Order;
+    use crate::turn::Turn;
+    use std::str::FromStr;
+
+    let card = |card: &str| Card::from_str(card).unwrap_or_else(|_| panic!("bad card {card}"));
+    let trump = Trump::Color(Color::Heart);
+
+    // West leads a spade, North cuts, East overtrumps, South follows.
+    let mut turn = Turn::new(3, Order::starting(Position::West));
+    let played = [
+        (Position::West, card("♠11")),
+        (Position::North, card("♥7")),
+        (Position::East, card("♥9")),
+        (Position::South, card("♠10")),
+    ];
+    for (position, card) in played {
+        assert!(turn.put(trump, position, &card));
+    }
+    assert!(!turn.put(trump, Position::West, &card("♣7")));
+    let Some(trick) = turn.take() else {
+        panic!("every position played");
+    };
+    assert_eq!(trick.number(), 3);
+    assert_eq!(trick.leader(), Position::West);
+    assert_eq!(trick.called_color(), Color::Spade);
+    assert_eq!(trick.winner(), Position::East);
+    assert_eq!(trick.team(), Team::WestEast);
+    assert_eq!(*trick.cards(), played);
+    assert_eq!(trick.card(Position::South), Some(card("♠10")));
+    assert_eq!(trick.points(trump), 11 + 14 + 10);
+}
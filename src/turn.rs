@@ -1,19 +1,20 @@
-use crate::card::{Card, Color};
+use crate::card::{Card, Color, Trump};
+use crate::card_set::CardSet;
+use crate::constants::MAX_PLAYERS;
 use crate::order::Order;
 use crate::position::Position;
 use crate::team::Team;
+use crate::trick::Trick;
 use std::fmt;
 use tracing::info;
 
-#[derive(Debug)]
+/// A trick being played, with the cards put so far in the order they were played.
+#[derive(Debug, Clone, Copy)]
 pub struct Turn {
     number: u64,
     order: Order,
     called_color: Option<Color>,
-    north: Option<Card>,
-    east: Option<Card>,
-    south: Option<Card>,
-    west: Option<Card>,
+    cards: [Option<(Position, Card)>; MAX_PLAYERS],
     master_position: Position,
 }
 
@@ -24,21 +25,27 @@ impl Turn {
             order,
             master_position: order.first(),
             called_color: None,
-            east: None,
-            north: None,
-            south: None,
-            west: None,
+            cards: [None; MAX_PLAYERS],
         }
     }
-    pub fn take(self) -> Option<[Card; 4]> {
-        let north = self.north?;
-        let east = self.east?;
-        let south = self.south?;
-        let west = self.west?;
-        let cards = [north, east, south, west];
-        Some(cards)
+    /// The trick won, once every position put its card.
+    pub const fn take(self) -> Option<Trick> {
+        let [Some(first), Some(second), Some(third), Some(fourth)] = self.cards else {
+            return None;
+        };
+        Some(Trick::new(
+            self.number,
+            [first, second, third, fourth],
+            self.master_position,
+        ))
     }
-    pub fn put(&mut self, trump_color: Color, position: Position, card: &Card) -> bool {
+    pub fn put(&mut self, trump: Trump, position: Position, card: &Card) -> bool {
+        if self.card(position).is_some() {
+            return false;
+        }
+        let Some(index) = self.cards.iter().position(Option::is_none) else {
+            return false;
+        };
         info!(
             "Turn put card {card} for position {position}, master card was {:?}",
             self.master_card()
@@ -50,61 +57,41 @@ impl Turn {
                 self.master_position = position;
             }
             Some(master_card) => {
-                if master_card.master(*card, trump_color) {
+                if CardSet::beating(master_card, trump).contains(*card) {
+                    info!("Master card is {card}, so player {position} becomes master");
+                    self.master_position = position;
+                } else {
                     info!(
                         "Master card is {master_card}, so player {} stays master",
                         self.master_position
                     );
-                } else {
-                    info!("Master card is {card}, so player {position} becomes master");
-                    self.master_position = position;
                 }
             }
         }
-        match position {
-            Position::North => {
-                if self.north.is_some() {
-                    return false;
-                }
-                self.north = Some(*card);
-            }
-            Position::East => {
-                if self.east.is_some() {
-                    return false;
-                }
-                self.east = Some(*card);
-            }
-            Position::South => {
-                if self.south.is_some() {
-                    return false;
-                }
-                self.south = Some(*card);
-            }
-            Position::West => {
-                if self.west.is_some() {
-                    return false;
-                }
-                self.west = Some(*card);
-            }
-        }
+        self.cards[index] = Some((position, *card));
         true
     }
     pub const fn is_first(&self) -> bool {
         self.called_color.is_none()
     }
     pub const fn finished(&self) -> bool {
-        self.north.is_some() && self.south.is_some() && self.east.is_some() && self.west.is_some()
+        self.cards[MAX_PLAYERS - 1].is_some()
     }
     pub const fn master_position(&self) -> Position {
         self.master_position
     }
-    pub const fn master_card(&self) -> Option<Card> {
-        match self.master_position {
-            Position::North => self.north,
-            Position::East => self.east,
-            Position::South => self.south,
-            Position::West => self.west,
-        }
+    /// Card put by `position`, if any.
+    pub fn card(&self, position: Position) -> Option<Card> {
+        self.cards()
+            .find(|(played_by, _)| *played_by == position)
+            .map(|(_, card)| card)
+    }
+    /// Cards put so far, in the order they were played.
+    pub fn cards(&self) -> impl Iterator<Item = (Position, Card)> + '_ {
+        self.cards.iter().flatten().copied()
+    }
+    pub fn master_card(&self) -> Option<Card> {
+        self.card(self.master_position)
     }
     pub fn master_color(&self) -> Option<Color> {
         self.master_card().map(|card| card.color())
@@ -118,21 +105,17 @@ impl Turn {
     pub const fn number(&self) -> u64 {
         self.number
     }
+    /// Order of play of the trick, its leader first.
+    pub const fn order(&self) -> Order {
+        self.order
+    }
 }
 
 impl fmt::Display for Turn {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f, "Turn: {}", self.number)?;
-        for position in self.order {
-            let card = match position {
-                Position::North => self.north,
-                Position::East => self.east,
-                Position::South => self.south,
-                Position::West => self.west,
-            };
-            if let Some(card) = card {
-                writeln!(f, "\t{position}: {card}")?;
-            }
+        for (position, card) in self.cards() {
+            writeln!(f, "\t{position}: {card}")?;
         }
         if let Some(card) = self.master_card() {
             write!(f, "\nMaster card: {card} ({})", self.master_position)?;
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Which game is played at the table.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    EnumIter,
+    EnumString,
+    Display,
+    Serialize,
+    Deserialize,
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum Variant {
+    /// Classic belote : 5 cards dealt, a returned card to take, then 3 more cards.
+    #[default]
+    Belote,
+    /// Coinche (contrée) : 8 cards dealt, then an auction on contracts which can be doubled.
+    Coinche,
+}
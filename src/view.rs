@@ -0,0 +1,238 @@
+use crate::announce::Announce;
+use crate::bidding::Bid;
+use crate::card::{Card, Trump};
+use crate::constants::MAX_PLAYERS;
+use crate::event::{GameEvent, Observer};
+use crate::hand::Hand;
+use crate::order::Order;
+use crate::points::Points;
+use crate::position::Position;
+use crate::rules::RuleSet;
+use crate::trick::Trick;
+use crate::variant::Variant;
+
+/// What a seat may legally know of the game : its own hand and everything shown to the table.
+///
+/// This is all an [`crate::agent::Agent`] receives. The view follows the public part of the
+/// events, the stack given when a deal starts being left out, and the game sets the hand of the
+/// seat before each of its decisions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlayerView {
+    position: Position,
+    hand: Hand,
+    number: u64,
+    order: Order,
+    variant: Variant,
+    rules: RuleSet,
+    card_returned: Option<Card>,
+    bids: Vec<(Position, Bid)>,
+    taker: Option<Position>,
+    trump: Option<Trump>,
+    announces: Vec<(Position, Announce)>,
+    belote: Option<Position>,
+    played: Vec<(Position, Card)>,
+    tricks: Vec<Trick>,
+    points: Points,
+    litige: u64,
+}
+
+impl PlayerView {
+    pub fn new(position: Position) -> Self {
+        Self {
+            position,
+            hand: Hand::default(),
+            number: 0,
+            order: Order::default(),
+            variant: Variant::default(),
+            rules: RuleSet::default(),
+            card_returned: None,
+            bids: Vec::new(),
+            taker: None,
+            trump: None,
+            announces: Vec::new(),
+            belote: None,
+            played: Vec::new(),
+            tricks: Vec::new(),
+            points: Points::default(),
+            litige: 0,
+        }
+    }
+
+    pub const fn position(&self) -> Position {
+        self.position
+    }
+    pub const fn hand(&self) -> Hand {
+        self.hand
+    }
+    pub const fn set_hand(&mut self, hand: Hand) {
+        self.hand = hand;
+    }
+    pub const fn number(&self) -> u64 {
+        self.number
+    }
+    pub const fn order(&self) -> Order {
+        self.order
+    }
+    pub const fn variant(&self) -> Variant {
+        self.variant
+    }
+    pub const fn rules(&self) -> RuleSet {
+        self.rules
+    }
+    /// Card returned for the bidding, `None` in coinche.
+    pub const fn card_returned(&self) -> Option<Card> {
+        self.card_returned
+    }
+    /// Bids of the deal, in order.
+    pub fn bids(&self) -> &[(Position, Bid)] {
+        &self.bids
+    }
+    pub const fn taker(&self) -> Option<Position> {
+        self.taker
+    }
+    pub const fn trump(&self) -> Option<Trump> {
+        self.trump
+    }
+    pub fn announces(&self) -> &[(Position, Announce)] {
+        &self.announces
+    }
+    /// Seat which announced belote.
+    pub const fn belote(&self) -> Option<Position> {
+        self.belote
+    }
+    /// Every card played during the deal, in order.
+    pub fn played(&self) -> &[(Position, Card)] {
+        &self.played
+    }
+    /// Cards of the trick being played, in order.
+    pub fn trick(&self) -> &[(Position, Card)] {
+        &self.played[self.played.len() - self.played.len() % MAX_PLAYERS..]
+    }
+    /// Tricks already won, in order.
+    pub fn tricks(&self) -> &[Trick] {
+        &self.tricks
+    }
+    /// Points of the game so far.
+    pub const fn points(&self) -> Points {
+        self.points
+    }
+    pub const fn set_points(&mut self, points: Points) {
+        self.points = points;
+    }
+    /// Points carried over from a litige, won by the next contract made.
+    pub const fn litige(&self) -> u64 {
+        self.litige
+    }
+}
+
+impl Observer for PlayerView {
+    fn notify(&mut self, event: &GameEvent) {
+        match *event {
+            GameEvent::DealStarted {
+                number,
+                order,
+                litige,
+                variant,
+                rules,
+                ..
+            } => {
+                *self = Self {
+                    number,
+                    order,
+                    litige,
+                    variant,
+                    rules,
+                    points: self.points,
+                    ..Self::new(self.position)
+                };
+            }
+            GameEvent::CardReturned { card } => self.card_returned = Some(card),
+            GameEvent::BidMade { position, bid } => self.bids.push((position, bid)),
+            GameEvent::TrumpChosen { taker, trump } => {
+                self.taker = Some(taker);
+                self.trump = Some(trump);
+            }
+            GameEvent::AnnounceDeclared { position, announce } => {
+                self.announces.push((position, announce));
+            }
+            GameEvent::Belote { position } => self.belote = Some(position),
+            GameEvent::CardPlayed { position, card } => self.played.push((position, card)),
+            GameEvent::TrickWon { trick, .. } => self.tricks.push(trick),
+            GameEvent::LitigeCarried { litige } => self.litige += litige,
+            GameEvent::Rebelote { .. }
+            | GameEvent::DealScored { .. }
+            | GameEvent::MatchEnded { .. } => {}
+        }
+    }
+}
+
+#[test]
+fn view_tests() {
+    use crate::stack::Stack;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut stack = Stack::random(&mut rng);
+    let mut view = PlayerView::new(Position::East);
+    view.notify(&GameEvent::DealStarted {
+        number: 3,
+        order: Order::default(),
+        stack,
+        litige: 40,
+        variant: Variant::default(),
+        rules: RuleSet::default(),
+    });
+    assert_eq!(view.number(), 3);
+    assert_eq!(view.litige(), 40);
+    assert!(view.hand().is_empty());
+    view.notify(&GameEvent::TrumpChosen {
+        taker: Position::North,
+        trump: Trump::AllTrump,
+    });
+    assert_eq!(view.taker(), Some(Position::North));
+
+    let mut play = |view: &mut PlayerView, position: Position| {
+        let Ok(card) = stack.give_card() else {
+            panic!("the stack should have cards");
+        };
+        view.notify(&GameEvent::CardPlayed { position, card });
+        card
+    };
+    let mut cards = Vec::new();
+    for position in Order::starting(Position::East) {
+        cards.push((position, play(&mut view, position)));
+    }
+    let Ok(cards) = <[(Position, Card); MAX_PLAYERS]>::try_from(cards) else {
+        panic!("four cards should be played");
+    };
+    view.notify(&GameEvent::TrickWon {
+        trick: Trick::new(1, cards, Position::South),
+        points: 0,
+    });
+    play(&mut view, Position::South);
+    play(&mut view, Position::West);
+    assert_eq!(view.played().len(), 6);
+    assert_eq!(view.tricks().len(), 1);
+    let trick = view.tricks()[0];
+    assert_eq!(trick.leader(), Position::East);
+    assert_eq!(trick.winner(), Position::South);
+    assert_eq!(*trick.cards(), cards);
+    assert_eq!(trick.card(Position::North), Some(cards[3].1));
+    assert_eq!(view.trick().len(), 2);
+    assert_eq!(view.trick()[0].0, Position::South);
+
+    // A new deal forgets the previous one, but not the position of the seat.
+    view.notify(&GameEvent::DealStarted {
+        number: 4,
+        order: Order::default(),
+        stack,
+        litige: 0,
+        variant: Variant::default(),
+        rules: RuleSet::default(),
+    });
+    assert_eq!(view.position(), Position::East);
+    assert!(view.played().is_empty());
+    assert_eq!(view.trick().len(), 0);
+    assert_eq!(view.taker(), None);
+}